@@ -0,0 +1,40 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use bevy::{app::ScheduleRunnerPlugin, log::LogPlugin, prelude::*};
+use clap::Parser;
+
+use bevy_game_gameplay::{blueprints::GameConfig, GameplayPlugin};
+use bevy_game_network::{NetworkServerPlugin, DEFAULT_PORT};
+
+// Run the gameplay at a fixed rate since there is no window to pace the frames
+const TICK_RATE: f64 = 60.;
+
+#[derive(Debug, Parser)]
+pub struct Arguments {
+    #[arg(short, long, default_value_t = SocketAddr::from((Ipv4Addr::UNSPECIFIED, DEFAULT_PORT)))]
+    pub address: SocketAddr,
+}
+
+fn main() {
+    let args = Arguments::parse();
+
+    App::new()
+        .add_plugins((
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+                1. / TICK_RATE,
+            ))),
+            LogPlugin::default(),
+        ))
+        // The server uses the default tuning, so it does not depend on the asset folder
+        .init_resource::<GameConfig>()
+        .add_plugins((
+            GameplayPlugin,
+            NetworkServerPlugin {
+                address: args.address,
+            },
+        ))
+        .run();
+}
@@ -0,0 +1,59 @@
+use bevy::{ecs::system::Command, prelude::*};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::blueprints::{BlueprintRegistry, SpawnBlueprint};
+
+// Spawns new entities from the registered blueprints, grouped by category
+pub struct SpawnWindow;
+
+#[derive(Default)]
+pub struct SpawnWindowState {
+    position: Vec2,
+}
+
+impl EditorWindow for SpawnWindow {
+    type State = SpawnWindowState;
+    const NAME: &'static str = "Spawn";
+
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let state = cx
+            .state_mut::<SpawnWindow>()
+            .expect("SpawnWindow state should exist");
+
+        ui.horizontal(|ui| {
+            ui.label("Position");
+            ui.add(egui::DragValue::new(&mut state.position.x).prefix("x: "));
+            ui.add(egui::DragValue::new(&mut state.position.y).prefix("y: "));
+        });
+
+        let Some(registry) = world.get_resource::<BlueprintRegistry>() else {
+            ui.label("No blueprints are registered");
+            return;
+        };
+        let mut categories: Vec<&str> = registry
+            .iter()
+            .map(|blueprint| blueprint.category)
+            .collect();
+        categories.sort();
+        categories.dedup();
+
+        let mut spawn = None;
+        for category in categories {
+            ui.collapsing(category, |ui| {
+                for blueprint in registry
+                    .iter()
+                    .filter(|blueprint| blueprint.category == category)
+                {
+                    if ui.button(blueprint.name).clicked() {
+                        spawn = Some(SpawnBlueprint::new(blueprint.name).at(state.position));
+                    }
+                }
+            });
+        }
+        if let Some(spawn) = spawn {
+            spawn.apply(world);
+        }
+    }
+}
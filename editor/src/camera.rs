@@ -0,0 +1,345 @@
+use bevy::{
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_editor_pls::{
+    default_windows::hierarchy::HierarchyWindow,
+    editor::Editor,
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::blueprints::GameCamera;
+
+use crate::paint::PaintTool;
+
+const FRAME_KEY: KeyCode = KeyCode::F;
+
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 10.;
+// How much the view zooms for each line scrolled
+const ZOOM_STEP: f32 = 1.1;
+// Scroll wheels that report pixels scroll about this many pixels per line
+const PIXELS_PER_LINE: f32 = 100.;
+// Framing a single small entity shows at least this much of the world around it
+const FRAME_MIN_SIZE: f32 = 256.;
+const FRAME_MARGIN: f32 = 1.25;
+
+pub struct EditorCameraPlugin;
+
+/// This plugin adds a camera to the game window that the editor can look through instead of the game camera,
+/// panned with the middle mouse button, zoomed with the scroll wheel, and framing the entities selected
+/// in the hierarchy with `F`. While it is in use, clicking a sprite selects its entity in the hierarchy.
+impl Plugin for EditorCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorView>()
+            .add_systems(Startup, spawn_editor_camera)
+            .add_systems(
+                Update,
+                (
+                    handle_frame_key,
+                    switch_camera.run_if(resource_changed::<EditorView>()),
+                    (
+                        navigate_editor_camera,
+                        frame_selected,
+                        select_clicked_sprite
+                            .run_if(|paint_tool: Res<PaintTool>| !paint_tool.enabled),
+                    )
+                        .run_if(|view: Res<EditorView>| view.enabled),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component)]
+struct EditorViewCamera;
+
+#[derive(Debug, Default)]
+#[derive(Resource)]
+pub struct EditorView {
+    // Whether the game window shows the editor camera instead of the game camera
+    pub enabled: bool,
+    frame_requested: bool,
+}
+
+impl EditorView {
+    // Switches to the editor camera if needed, then frames the selected entities
+    pub fn frame_selected(&mut self) {
+        self.enabled = true;
+        self.frame_requested = true;
+    }
+}
+
+fn spawn_editor_camera(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Editor Camera"),
+        Camera2dBundle {
+            camera: Camera {
+                is_active: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        EditorViewCamera,
+    ));
+}
+
+// Only one camera draws to the game window at a time
+// The editor camera starts from the view of the game camera, so switching does not jump
+fn switch_camera(
+    view: Res<EditorView>,
+    mut editor_camera_query: Query<
+        (&mut Camera, &mut Transform, &mut OrthographicProjection),
+        (With<EditorViewCamera>, Without<GameCamera>),
+    >,
+    mut game_camera_query: Query<
+        (&mut Camera, &Transform, &OrthographicProjection),
+        (With<GameCamera>, Without<EditorViewCamera>),
+    >,
+) {
+    let Ok((mut editor_camera, mut editor_transform, mut editor_projection)) =
+        editor_camera_query.get_single_mut()
+    else {
+        return;
+    };
+    if editor_camera.is_active == view.enabled {
+        return;
+    }
+    editor_camera.is_active = view.enabled;
+    for (mut game_camera, game_transform, game_projection) in game_camera_query.iter_mut() {
+        game_camera.is_active = !view.enabled;
+        if view.enabled {
+            *editor_transform = *game_transform;
+            editor_projection.scale = game_projection.scale;
+        }
+    }
+}
+
+// Keyboard input is shared by every window, so this only listens while the game window has focus
+fn handle_frame_key(
+    inputs: Res<Input<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut view: ResMut<EditorView>,
+) {
+    let focused = window_query.get_single().is_ok_and(|window| window.focused);
+    if focused && inputs.just_pressed(FRAME_KEY) {
+        view.frame_selected();
+    }
+}
+
+// The offset of the cursor from the center of the window, in pixels with y pointing up like the world
+fn cursor_offset(window: &Window) -> Option<Vec2> {
+    let cursor = window.cursor_position()?;
+    let offset = cursor - Vec2::new(window.width(), window.height()) / 2.;
+    Some(Vec2::new(offset.x, -offset.y))
+}
+
+fn navigate_editor_camera(
+    mouse: Res<Input<MouseButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut wheel_events: EventReader<MouseWheel>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<EditorViewCamera>>,
+) {
+    let motion: Vec2 = motion_events.read().map(|event| event.delta).sum();
+    let scroll: f32 = wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        })
+        .sum();
+    // Mouse input is shared by every window as well, so only the cursor over the game window moves the camera
+    let Some(offset) = window_query.get_single().ok().and_then(cursor_offset) else {
+        return;
+    };
+    let Ok((mut transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    if mouse.pressed(MouseButton::Middle) {
+        transform.translation -= Vec3::new(motion.x, -motion.y, 0.) * projection.scale;
+    }
+    if scroll != 0. {
+        // Zooms around the cursor, so the point under it stays put
+        let cursor = transform.translation.xy() + offset * projection.scale;
+        projection.scale = (projection.scale * ZOOM_STEP.powf(-scroll)).clamp(MIN_ZOOM, MAX_ZOOM);
+        let translation = cursor - offset * projection.scale;
+        transform.translation = translation.extend(transform.translation.z);
+    }
+}
+
+// The size of a sprite before its transform is applied
+fn sprite_size(sprite: &Sprite, image: &Handle<Image>, images: &Assets<Image>) -> Option<Vec2> {
+    sprite
+        .custom_size
+        .or_else(|| images.get(image).map(|image| image.size_f32()))
+}
+
+fn frame_selected(
+    mut view: ResMut<EditorView>,
+    editor: Res<Editor>,
+    images: Res<Assets<Image>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    entity_query: Query<(&GlobalTransform, Option<(&Sprite, &Handle<Image>)>)>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<EditorViewCamera>>,
+) {
+    if !view.frame_requested {
+        return;
+    }
+    view.frame_requested = false;
+
+    let Some(hierarchy) = editor.window_state::<HierarchyWindow>() else {
+        return;
+    };
+    let bounds = hierarchy
+        .selected
+        .iter()
+        .filter_map(|entity| entity_query.get(entity).ok())
+        .map(|(transform, sprite)| {
+            let position = transform.translation().xy();
+            let size = sprite
+                .and_then(|(sprite, image)| sprite_size(sprite, image, &images))
+                .unwrap_or_default()
+                * transform.compute_transform().scale.xy();
+            (position - size / 2., position + size / 2.)
+        })
+        .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)));
+    let Some((min, max)) = bounds else {
+        info!("Select entities in the hierarchy to frame them");
+        return;
+    };
+    let (Ok(window), Ok((mut transform, mut projection))) =
+        (window_query.get_single(), camera_query.get_single_mut())
+    else {
+        return;
+    };
+
+    let size = (max - min).max(Vec2::splat(FRAME_MIN_SIZE)) * FRAME_MARGIN;
+    let center = (min + max) / 2.;
+    transform.translation = center.extend(transform.translation.z);
+    projection.scale = (size.x / window.width())
+        .max(size.y / window.height())
+        .clamp(MIN_ZOOM, MAX_ZOOM);
+}
+
+// Selects the sprite under the cursor in the hierarchy, the one drawn on top if several overlap
+// Holding Ctrl adds it to the selection instead
+fn select_clicked_sprite(
+    mouse: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    mut editor: ResMut<Editor>,
+    images: Res<Assets<Image>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<EditorViewCamera>>,
+    sprite_query: Query<(
+        Entity,
+        &Sprite,
+        &Handle<Image>,
+        &GlobalTransform,
+        &ViewVisibility,
+    )>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor) = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+    else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(position) = camera.viewport_to_world_2d(camera_transform, cursor) else {
+        return;
+    };
+
+    let clicked = sprite_query
+        .iter()
+        .filter(|(_, _, _, _, visibility)| visibility.get())
+        .filter(|(_, sprite, image, transform, _)| {
+            let Some(size) = sprite_size(sprite, image, &images) else {
+                return false;
+            };
+            let local = transform
+                .affine()
+                .inverse()
+                .transform_point3(position.extend(0.))
+                .xy();
+            // The anchor moves the sprite relative to its transform
+            let center = -sprite.anchor.as_vec() * size;
+            let distance = (local - center).abs();
+            distance.x <= size.x / 2. && distance.y <= size.y / 2.
+        })
+        .max_by(|(_, _, _, a, _), (_, _, _, b, _)| a.translation().z.total_cmp(&b.translation().z))
+        .map(|(entity, ..)| entity);
+
+    let add = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let Some(hierarchy) = editor.window_state_mut::<HierarchyWindow>() else {
+        return;
+    };
+    match clicked {
+        Some(entity) => hierarchy.selected.select_maybe_add(entity, add),
+        None if !add => hierarchy.selected.clear(),
+        None => {}
+    }
+}
+
+pub struct CameraWindow;
+
+impl EditorWindow for CameraWindow {
+    type State = ();
+    const NAME: &'static str = "Editor Camera";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let mut view = world.resource_mut::<EditorView>();
+        // Only touch the resource when something changes, since changing it switches cameras
+        let mut enabled = view.enabled;
+        if ui
+            .checkbox(
+                &mut enabled,
+                "Show the game window through the editor camera",
+            )
+            .changed()
+        {
+            view.enabled = enabled;
+        }
+        if ui.button("Frame selected").clicked() {
+            view.frame_selected();
+        }
+        if !enabled {
+            return;
+        }
+
+        let mut camera_query = world
+            .query_filtered::<(&Transform, &mut OrthographicProjection), With<EditorViewCamera>>();
+        if let Ok((transform, mut projection)) = camera_query.get_single_mut(world) {
+            ui.label(format!(
+                "Position: ({:.0}, {:.0})",
+                transform.translation.x, transform.translation.y
+            ));
+            let mut zoom = projection.scale;
+            ui.horizontal(|ui| {
+                ui.label("Zoom");
+                ui.add(
+                    egui::Slider::new(&mut zoom, MIN_ZOOM..=MAX_ZOOM)
+                        .logarithmic(true)
+                        .max_decimals(2),
+                );
+            });
+            if zoom != projection.scale {
+                projection.scale = zoom;
+            }
+        }
+
+        ui.separator();
+        ui.label("Drag with the middle mouse button to pan and scroll to zoom");
+        ui.label("Press F in the game window to frame the entities selected in the hierarchy");
+        ui.label("Click a sprite to select it, or Ctrl+click to add it to the selection");
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use bevy::{
+    app::AppExit,
+    core::{update_frame_count, FrameCount},
+    prelude::*,
+    winit::WinitSettings,
+};
+use bevy_game::GameState;
+
+// Runs the editor for a number of frames and exits, for scripts and CI
+pub struct FrameLimitPlugin {
+    pub frames: u32,
+    // The state the game should be in once the frames ran, if not just out of `GameState::Loading`
+    pub expected_state: Option<GameState>,
+    pub status: FrameLimitStatus,
+}
+
+/// This plugin exits the app after a number of frames, and sets its status to passed if the game
+/// finished loading and reached the expected state
+impl Plugin for FrameLimitPlugin {
+    fn build(&self, app: &mut App) {
+        // Otherwise the winit runner exits the process itself, before `main` can return the status
+        app.world
+            .get_resource_or_insert_with(WinitSettings::default)
+            .return_from_run = true;
+        app.insert_resource(FrameLimit {
+            frames: self.frames,
+            expected_state: self.expected_state,
+            status: self.status.clone(),
+        })
+        .add_systems(Last, exit_after_frames.after(update_frame_count));
+    }
+}
+
+// Whether the frames ran and the game passed, shared with `main` because `App::run` does not give the app back
+#[derive(Clone, Debug, Default)]
+pub struct FrameLimitStatus(Arc<AtomicBool>);
+
+impl FrameLimitStatus {
+    // A failure unless every frame ran, such as when the window was closed early
+    pub fn exit_code(&self) -> ExitCode {
+        match self.0.load(Ordering::Relaxed) {
+            true => ExitCode::SUCCESS,
+            false => ExitCode::FAILURE,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+struct FrameLimit {
+    frames: u32,
+    expected_state: Option<GameState>,
+    status: FrameLimitStatus,
+}
+
+fn exit_after_frames(
+    limit: Res<FrameLimit>,
+    frame_count: Res<FrameCount>,
+    state: Res<State<GameState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if frame_count.0 < limit.frames {
+        return;
+    }
+    let state = *state.get();
+    let passed = match limit.expected_state {
+        Some(expected) => state == expected,
+        None => state != GameState::Loading,
+    };
+    match (passed, limit.expected_state) {
+        (true, _) => info!("Ran {} frames and ended in {state:?}", limit.frames),
+        (false, Some(expected)) => error!(
+            "Ran {} frames and ended in {state:?} instead of {expected:?}",
+            limit.frames
+        ),
+        (false, None) => error!("Ran {} frames and the game is still loading", limit.frames),
+    }
+    limit.status.0.store(passed, Ordering::Relaxed);
+    exit.send(AppExit);
+}
@@ -0,0 +1,715 @@
+use std::{any::TypeId, time::Duration};
+
+use bevy::{
+    ecs::component::Tick,
+    prelude::*,
+    reflect::TypeRegistry,
+    utils::{HashMap, HashSet},
+};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::blueprints::BlueprintRegistry;
+
+// Older entries are dropped once the history is this long
+const MAX_ENTRIES: usize = 100;
+// Changes to the same entities this close together are merged into one entry, such as when dragging a value
+const MERGE_WINDOW: Duration = Duration::from_millis(500);
+
+pub struct HistoryPlugin;
+
+/// This plugin records spawns, despawns and component edits of entities made from blueprints
+/// as reflected diffs while virtual time is paused, so they can be undone with Ctrl+Z,
+/// redone with Ctrl+Shift+Z, or browsed in the "History" window
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorHistory>()
+            .add_systems(Update, handle_history_keys)
+            .add_systems(Last, track_history);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryAction {
+    Undo,
+    Redo,
+}
+
+type Components = HashMap<TypeId, Box<dyn Reflect>>;
+
+#[derive(Debug)]
+enum Change {
+    Spawned {
+        entity: Entity,
+        components: Components,
+    },
+    Despawned {
+        entity: Entity,
+        components: Components,
+    },
+    // A component was inserted, edited or removed
+    Component {
+        entity: Entity,
+        component: TypeId,
+        before: Option<Box<dyn Reflect>>,
+        after: Option<Box<dyn Reflect>>,
+    },
+}
+
+impl Change {
+    fn entity(&self) -> Entity {
+        match self {
+            Change::Spawned { entity, .. }
+            | Change::Despawned { entity, .. }
+            | Change::Component { entity, .. } => *entity,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HistoryEntry {
+    pub label: String,
+    changes: Vec<Change>,
+    // The real time of the last change merged into this entry
+    recorded_at: Duration,
+}
+
+#[derive(Default)]
+#[derive(Resource)]
+pub struct EditorHistory {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    pending: Vec<HistoryAction>,
+    // The reflected components of every tracked entity when the history was last checked
+    snapshot: HashMap<Entity, Components>,
+    // Undoing a despawn spawns a new entity, which takes the place of the old one in older entries
+    respawned: HashMap<Entity, Entity>,
+    last_check: Option<Tick>,
+}
+
+impl EditorHistory {
+    // The entries that can be undone, oldest first
+    pub fn undo_entries(&self) -> &[HistoryEntry] {
+        &self.undo
+    }
+
+    // The entries that can be redone, the next one to redo last
+    pub fn redo_entries(&self) -> &[HistoryEntry] {
+        &self.redo
+    }
+
+    // Queues an undo or redo, applied at the end of the frame
+    pub fn request(&mut self, action: HistoryAction) {
+        self.pending.push(action);
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.respawned.clear();
+    }
+
+    fn record(&mut self, label: String, changes: Vec<Change>, now: Duration) {
+        self.redo.clear();
+        if let Some(last) = self.undo.last_mut() {
+            let entities: HashSet<Entity> = last.changes.iter().map(Change::entity).collect();
+            let mergeable = now.saturating_sub(last.recorded_at) < MERGE_WINDOW
+                && changes.iter().all(|change| {
+                    matches!(change, Change::Component { .. })
+                        && entities.contains(&change.entity())
+                });
+            if mergeable {
+                merge(&mut last.changes, changes);
+                last.recorded_at = now;
+                return;
+            }
+        }
+        self.undo.push(HistoryEntry {
+            label,
+            changes,
+            recorded_at: now,
+        });
+        if self.undo.len() > MAX_ENTRIES {
+            self.undo.remove(0);
+        }
+    }
+
+    fn resolve(&self, entity: Entity) -> Entity {
+        self.respawned.get(&entity).copied().unwrap_or(entity)
+    }
+
+    fn apply(&mut self, world: &mut World, type_registry: &TypeRegistry, action: HistoryAction) {
+        let (from, undo) = match action {
+            HistoryAction::Undo => (&mut self.undo, true),
+            HistoryAction::Redo => (&mut self.redo, false),
+        };
+        let Some(entry) = from.pop() else {
+            return;
+        };
+        if undo {
+            for change in entry.changes.iter().rev() {
+                self.apply_change(world, type_registry, change, true);
+            }
+            self.redo.push(entry);
+        } else {
+            for change in entry.changes.iter() {
+                self.apply_change(world, type_registry, change, false);
+            }
+            self.undo.push(entry);
+        }
+    }
+
+    fn apply_change(
+        &mut self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+        change: &Change,
+        undo: bool,
+    ) {
+        match (change, undo) {
+            (Change::Spawned { entity, .. }, true) | (Change::Despawned { entity, .. }, false) => {
+                let entity = self.resolve(*entity);
+                if let Some(entity) = world.get_entity_mut(entity) {
+                    entity.despawn_recursive();
+                }
+            }
+            (Change::Spawned { entity, components }, false)
+            | (Change::Despawned { entity, components }, true) => {
+                let mut respawned = world.spawn_empty();
+                for (type_id, value) in components.iter() {
+                    if let Some(reflect_component) = reflect_component(type_registry, *type_id) {
+                        reflect_component.insert(&mut respawned, value.as_ref());
+                    }
+                }
+                let respawned = respawned.id();
+                // Entries that refer to the entity by any of its previous ids now refer to the new one
+                let previous = self.resolve(*entity);
+                for replacement in self.respawned.values_mut() {
+                    if *replacement == previous {
+                        *replacement = respawned;
+                    }
+                }
+                self.respawned.insert(*entity, respawned);
+                self.respawned.insert(previous, respawned);
+            }
+            (
+                Change::Component {
+                    entity,
+                    component,
+                    before,
+                    after,
+                },
+                undo,
+            ) => {
+                let Some(reflect_component) = reflect_component(type_registry, *component) else {
+                    return;
+                };
+                let Some(mut entity) = world.get_entity_mut(self.resolve(*entity)) else {
+                    return;
+                };
+                match if undo { before } else { after } {
+                    Some(value) => reflect_component.apply_or_insert(&mut entity, value.as_ref()),
+                    None => reflect_component.remove(&mut entity),
+                }
+            }
+        }
+    }
+}
+
+// Folds consecutive changes into an entry, keeping the first `before` of each component
+fn merge(changes: &mut Vec<Change>, new_changes: Vec<Change>) {
+    for change in new_changes {
+        let Change::Component {
+            entity,
+            component,
+            after,
+            ..
+        } = &change
+        else {
+            changes.push(change);
+            continue;
+        };
+        let existing = changes.iter_mut().find_map(|existing| match existing {
+            Change::Component {
+                entity: existing_entity,
+                component: existing_component,
+                after: existing_after,
+                ..
+            } if existing_entity == entity && existing_component == component => {
+                Some(existing_after)
+            }
+            _ => None,
+        });
+        match existing {
+            Some(existing_after) => {
+                *existing_after = after.as_ref().map(|value| value.clone_value());
+            }
+            None => changes.push(change),
+        }
+    }
+}
+
+fn reflect_component(type_registry: &TypeRegistry, type_id: TypeId) -> Option<&ReflectComponent> {
+    type_registry.get(type_id)?.data::<ReflectComponent>()
+}
+
+// Components that other systems compute from the ones that are edited
+fn is_derived(type_id: TypeId) -> bool {
+    [
+        TypeId::of::<GlobalTransform>(),
+        TypeId::of::<InheritedVisibility>(),
+        TypeId::of::<ViewVisibility>(),
+        TypeId::of::<Parent>(),
+        TypeId::of::<Children>(),
+    ]
+    .contains(&type_id)
+}
+
+// Entities made from a blueprint are the ones the editor edits, and the ones saved in level scenes
+fn tracked_entities(world: &World) -> HashSet<Entity> {
+    let registry = world.resource::<BlueprintRegistry>();
+    world
+        .iter_entities()
+        .filter(|entity| registry.matches(entity))
+        .map(|entity| entity.id())
+        .collect()
+}
+
+fn reflected_components<'a>(
+    world: &World,
+    type_registry: &'a TypeRegistry,
+    entity: Entity,
+) -> Vec<(TypeId, &'a ReflectComponent)> {
+    world
+        .inspect_entity(entity)
+        .iter()
+        .filter_map(|info| info.type_id())
+        .filter(|type_id| !is_derived(*type_id))
+        .filter_map(|type_id| Some((type_id, reflect_component(type_registry, type_id)?)))
+        .collect()
+}
+
+fn snapshot_entity(world: &World, type_registry: &TypeRegistry, entity: Entity) -> Components {
+    reflected_components(world, type_registry, entity)
+        .into_iter()
+        .filter_map(|(type_id, reflect_component)| {
+            let value = reflect_component.reflect(world.entity(entity))?;
+            Some((type_id, value.clone_value()))
+        })
+        .collect()
+}
+
+fn snapshot_world(world: &World, type_registry: &TypeRegistry) -> HashMap<Entity, Components> {
+    tracked_entities(world)
+        .into_iter()
+        .map(|entity| (entity, snapshot_entity(world, type_registry, entity)))
+        .collect()
+}
+
+fn clone_components(components: &Components) -> Components {
+    components
+        .iter()
+        .map(|(type_id, value)| (*type_id, value.clone_value()))
+        .collect()
+}
+
+// Compares the world with the snapshot, updating the snapshot as it goes
+fn diff_world(
+    world: &World,
+    type_registry: &TypeRegistry,
+    snapshot: &mut HashMap<Entity, Components>,
+    last_check: Tick,
+    this_run: Tick,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let entities = tracked_entities(world);
+    for &entity in entities.iter() {
+        let Some(before) = snapshot.get_mut(&entity) else {
+            let components = snapshot_entity(world, type_registry, entity);
+            changes.push(Change::Spawned {
+                entity,
+                components: clone_components(&components),
+            });
+            snapshot.insert(entity, components);
+            continue;
+        };
+
+        let entity_ref = world.entity(entity);
+        let current = reflected_components(world, type_registry, entity);
+        for (type_id, reflect_component) in current.iter() {
+            let changed = world
+                .components()
+                .get_id(*type_id)
+                .and_then(|component_id| entity_ref.get_change_ticks_by_id(component_id))
+                .is_some_and(|ticks| ticks.is_changed(last_check, this_run));
+            if !changed {
+                continue;
+            }
+            let Some(value) = reflect_component.reflect(entity_ref) else {
+                continue;
+            };
+            let previous = before.get(type_id);
+            // Systems and inspectors can mark a component as changed without changing it
+            if previous.is_some_and(|previous| previous.reflect_partial_eq(value) == Some(true)) {
+                continue;
+            }
+            changes.push(Change::Component {
+                entity,
+                component: *type_id,
+                before: previous.map(|previous| previous.clone_value()),
+                after: Some(value.clone_value()),
+            });
+            before.insert(*type_id, value.clone_value());
+        }
+
+        let removed: Vec<TypeId> = before
+            .keys()
+            .filter(|type_id| !current.iter().any(|(current, _)| current == *type_id))
+            .copied()
+            .collect();
+        for type_id in removed {
+            changes.push(Change::Component {
+                entity,
+                component: type_id,
+                before: before.remove(&type_id),
+                after: None,
+            });
+        }
+    }
+
+    let despawned: Vec<Entity> = snapshot
+        .keys()
+        .filter(|entity| !entities.contains(*entity))
+        .copied()
+        .collect();
+    for entity in despawned {
+        if let Some(components) = snapshot.remove(&entity) {
+            changes.push(Change::Despawned { entity, components });
+        }
+    }
+    changes
+}
+
+fn entity_name(world: &World, entity: Entity, components: Option<&Components>) -> String {
+    world
+        .get::<Name>(entity)
+        .cloned()
+        .or_else(|| {
+            let name = components?.get(&TypeId::of::<Name>())?;
+            Name::from_reflect(name.as_ref())
+        })
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{entity:?}"))
+}
+
+fn describe_change(world: &World, type_registry: &TypeRegistry, change: &Change) -> String {
+    match change {
+        Change::Spawned { entity, components } => {
+            format!("Spawn {}", entity_name(world, *entity, Some(components)))
+        }
+        Change::Despawned { entity, components } => {
+            format!("Despawn {}", entity_name(world, *entity, Some(components)))
+        }
+        Change::Component {
+            entity,
+            component,
+            before,
+            after,
+        } => {
+            let verb = match (before, after) {
+                (None, _) => "Insert",
+                (_, None) => "Remove",
+                _ => "Edit",
+            };
+            let component = type_registry
+                .get(*component)
+                .map(|registration| registration.type_info().type_path_table().short_path())
+                .unwrap_or("component");
+            format!(
+                "{verb} {component} on {}",
+                entity_name(world, *entity, None)
+            )
+        }
+    }
+}
+
+fn describe(world: &World, type_registry: &TypeRegistry, changes: &[Change]) -> String {
+    let first = describe_change(world, type_registry, &changes[0]);
+    match changes.len() {
+        1 => first,
+        count => format!("{first} and {} more", count - 1),
+    }
+}
+
+// Keyboard input is shared by every window, so these work when the editor window has focus
+fn handle_history_keys(inputs: Res<Input<KeyCode>>, mut history: ResMut<EditorHistory>) {
+    let control = inputs.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let shift = inputs.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if control && inputs.just_pressed(KeyCode::Z) {
+        history.request(if shift {
+            HistoryAction::Redo
+        } else {
+            HistoryAction::Undo
+        });
+    }
+}
+
+// Edits are only recorded while virtual time is paused, so the simulation does not fill the history
+fn track_history(world: &mut World) {
+    let recording = world.resource::<Time<Virtual>>().is_paused();
+    let now = world.resource::<Time<Real>>().elapsed();
+    world.resource_scope(|world, mut history: Mut<EditorHistory>| {
+        if !recording {
+            history.last_check = None;
+            history.pending.clear();
+            return;
+        }
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        match history.last_check {
+            Some(last_check) => {
+                let this_run = world.change_tick();
+                let changes = diff_world(
+                    world,
+                    &type_registry,
+                    &mut history.snapshot,
+                    last_check,
+                    this_run,
+                );
+                if !changes.is_empty() {
+                    let label = describe(world, &type_registry, &changes);
+                    history.record(label, changes, now);
+                }
+            }
+            // The world changed while the history was not recording
+            None => history.snapshot = snapshot_world(world, &type_registry),
+        }
+
+        let actions = std::mem::take(&mut history.pending);
+        if !actions.is_empty() {
+            for action in actions {
+                history.apply(world, &type_registry, action);
+            }
+            // Applying the history is not an edit of its own
+            history.snapshot = snapshot_world(world, &type_registry);
+        }
+        history.last_check = Some(world.change_tick());
+        world.increment_change_tick();
+    });
+}
+
+pub struct HistoryWindow;
+
+impl EditorWindow for HistoryWindow {
+    type State = ();
+    const NAME: &'static str = "History";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let paused = world.resource::<Time<Virtual>>().is_paused();
+        let mut history = world.resource_mut::<EditorHistory>();
+
+        // The simulation changes the same components as the inspector, so edits are only told apart while paused
+        if !paused {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "Not recording: edits made while virtual time runs cannot be undone. \
+                 Pause with the Pause key to record them.",
+            );
+        }
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(paused, |ui| {
+                if ui.button("Undo").clicked() {
+                    history.request(HistoryAction::Undo);
+                }
+                if ui.button("Redo").clicked() {
+                    history.request(HistoryAction::Redo);
+                }
+            });
+            if ui.button("Clear").clicked() {
+                history.clear();
+            }
+        });
+        ui.separator();
+
+        let undo_count = history.undo_entries().len();
+        let mut requests = Vec::new();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            // Clicking an entry undoes or redoes everything up to it
+            for (index, entry) in history.undo_entries().iter().enumerate() {
+                let current = index + 1 == undo_count;
+                if ui.selectable_label(current, &entry.label).clicked() && paused {
+                    requests.extend(
+                        std::iter::repeat(HistoryAction::Undo).take(undo_count - index - 1),
+                    );
+                }
+            }
+            for (index, entry) in history.redo_entries().iter().rev().enumerate() {
+                let label = egui::RichText::new(&entry.label).weak();
+                if ui.selectable_label(false, label).clicked() && paused {
+                    requests.extend(std::iter::repeat(HistoryAction::Redo).take(index + 1));
+                }
+            }
+        });
+        for request in requests {
+            history.request(request);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{input::InputPlugin, time::TimeUpdateStrategy};
+    use bevy_game::blueprints::{BlueprintsPlugin, Collider, Pickup, PickupKind, PickupRespawn};
+
+    use super::*;
+
+    // Frames this far apart are never merged into one entry
+    const FRAME: Duration = Duration::from_secs(1);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            TransformPlugin,
+            BlueprintsPlugin,
+            HistoryPlugin,
+        ))
+        .register_type::<Pickup>()
+        .register_type::<PickupKind>()
+        .register_type::<PickupRespawn>()
+        .register_type::<Collider>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.world.resource_mut::<Time<Virtual>>().pause();
+        app.update();
+        app
+    }
+
+    fn apply(app: &mut App, action: HistoryAction) {
+        app.world.resource_mut::<EditorHistory>().request(action);
+        app.update();
+    }
+
+    fn labels(app: &App) -> Vec<String> {
+        let history = app.world.resource::<EditorHistory>();
+        history
+            .undo_entries()
+            .iter()
+            .map(|entry| entry.label.clone())
+            .collect()
+    }
+
+    // The id an entity has now, after the history respawned it any number of times
+    fn current(app: &App, entity: Entity) -> Entity {
+        app.world.resource::<EditorHistory>().resolve(entity)
+    }
+
+    fn translation(app: &App, entity: Entity) -> Option<Vec3> {
+        let transform = app.world.get::<Transform>(current(app, entity))?;
+        Some(transform.translation)
+    }
+
+    fn move_to(app: &mut App, entity: Entity, translation: Vec3) {
+        app.world.get_mut::<Transform>(entity).unwrap().translation = translation;
+    }
+
+    fn pickup_count(app: &mut App) -> usize {
+        app.world.query::<&Pickup>().iter(&app.world).count()
+    }
+
+    #[test]
+    fn undo_and_redo_respawn_despawned_entities() {
+        let mut app = app();
+        let entity = app.world.spawn(Pickup::coin(Vec2::new(1., 2.))).id();
+        app.update();
+        move_to(&mut app, entity, Vec3::new(3., 4., 0.));
+        app.update();
+        app.world.entity_mut(entity).despawn_recursive();
+        app.update();
+        assert_eq!(
+            labels(&app),
+            ["Spawn Coin", "Edit Transform on Coin", "Despawn Coin"]
+        );
+
+        // The despawned entity comes back with a new id, and the older entries follow it
+        apply(&mut app, HistoryAction::Undo);
+        let respawned = current(&app, entity);
+        assert_ne!(respawned, entity);
+        assert_eq!(translation(&app, entity), Some(Vec3::new(3., 4., 0.)));
+        let pickup = app.world.get::<Pickup>(respawned).unwrap();
+        assert_eq!(pickup.kind, PickupKind::Coin);
+        assert_eq!(pickup_count(&mut app), 1);
+
+        apply(&mut app, HistoryAction::Undo);
+        assert_eq!(current(&app, entity), respawned);
+        assert_eq!(translation(&app, entity), Some(Vec3::new(1., 2., 0.)));
+
+        apply(&mut app, HistoryAction::Undo);
+        assert_eq!(pickup_count(&mut app), 0);
+        assert!(labels(&app).is_empty());
+
+        apply(&mut app, HistoryAction::Redo);
+        apply(&mut app, HistoryAction::Redo);
+        let redone = current(&app, entity);
+        assert_ne!(redone, respawned);
+        assert_eq!(translation(&app, entity), Some(Vec3::new(3., 4., 0.)));
+        assert_eq!(pickup_count(&mut app), 1);
+
+        apply(&mut app, HistoryAction::Redo);
+        assert!(app.world.get_entity(redone).is_none());
+        assert_eq!(pickup_count(&mut app), 0);
+        assert_eq!(labels(&app).len(), 3);
+    }
+
+    #[test]
+    fn edits_close_together_are_merged() {
+        let mut app = app();
+        let entity = app.world.spawn(Pickup::coin(Vec2::ZERO)).id();
+        app.update();
+        move_to(&mut app, entity, Vec3::X);
+        app.update();
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(MERGE_WINDOW / 10));
+        for x in [2., 3.] {
+            move_to(&mut app, entity, Vec3::new(x, 0., 0.));
+            app.update();
+        }
+        assert_eq!(labels(&app), ["Spawn Coin", "Edit Transform on Coin"]);
+
+        // Spawns are never merged, even close to an edit
+        app.world.spawn(Pickup::heal(Vec2::ZERO));
+        app.update();
+        assert_eq!(labels(&app).len(), 3);
+
+        apply(&mut app, HistoryAction::Undo);
+        apply(&mut app, HistoryAction::Undo);
+        assert_eq!(translation(&app, entity), Some(Vec3::ZERO));
+        apply(&mut app, HistoryAction::Redo);
+        assert_eq!(translation(&app, entity), Some(Vec3::new(3., 0., 0.)));
+    }
+
+    #[test]
+    fn removed_components_are_inserted_back() {
+        let mut app = app();
+        let entity = app.world.spawn(Pickup::coin(Vec2::ZERO)).id();
+        app.update();
+        app.world.entity_mut(entity).remove::<Collider>();
+        app.update();
+        assert_eq!(labels(&app), ["Spawn Coin", "Remove Collider on Coin"]);
+
+        apply(&mut app, HistoryAction::Undo);
+        let collider = app.world.get::<Collider>(entity).unwrap();
+        assert_eq!(collider.radius, Pickup::RADIUS);
+
+        apply(&mut app, HistoryAction::Redo);
+        assert!(app.world.get::<Collider>(entity).is_none());
+        assert!(app.world.get::<Pickup>(entity).is_some());
+    }
+}
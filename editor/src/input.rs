@@ -0,0 +1,195 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::{
+    blueprints::{CharacterMovement, MovementContribution},
+    controls::{
+        describe_bindings, ActionState, CharacterControl, Controller, InputMap, VirtualInput,
+    },
+    gameplay::GameplaySet,
+};
+
+pub struct InputInspectorPlugin;
+
+/// This plugin samples what each controller sees every frame for the "Input" window
+impl Plugin for InputInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputSamples>().add_systems(
+            Update,
+            // Gameplay clears the movement intent once it is applied
+            sample_inputs.before(GameplaySet),
+        );
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct InputSample {
+    pressed: Vec<CharacterControl>,
+    axis: Vec2,
+    contributions: Vec<MovementContribution>,
+    resolved: Option<MovementContribution>,
+}
+
+#[derive(Debug, Default)]
+#[derive(Resource)]
+struct InputSamples(HashMap<Entity, InputSample>);
+
+fn sample_inputs(
+    mut samples: ResMut<InputSamples>,
+    input_query: Query<(
+        Entity,
+        &ActionState<CharacterControl>,
+        Option<&CharacterMovement>,
+    )>,
+) {
+    samples.0 = input_query
+        .iter()
+        .map(|(entity, action_state, movement)| {
+            let sample = InputSample {
+                pressed: action_state.get_pressed(),
+                axis: action_state
+                    .axis_pair(CharacterControl::Move)
+                    .map(|axis| axis.xy())
+                    .unwrap_or_default(),
+                contributions: movement
+                    .map(|movement| movement.contributions().to_vec())
+                    .unwrap_or_default(),
+                resolved: movement.and_then(|movement| movement.resolved()),
+            };
+            (entity, sample)
+        })
+        .collect();
+}
+
+// Lists every entity with an `ActionState<CharacterControl>`, and injects presses into them
+pub struct InputWindow;
+
+impl EditorWindow for InputWindow {
+    type State = ();
+    const NAME: &'static str = "Input";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let mut input_query = world.query_filtered::<Entity, With<ActionState<CharacterControl>>>();
+        let entities: Vec<Entity> = input_query.iter(world).collect();
+        if entities.is_empty() {
+            ui.label("No entity has an ActionState<CharacterControl>");
+            return;
+        }
+
+        for entity in entities {
+            let title = match world.get::<Name>(entity) {
+                Some(name) => format!("{name} ({entity:?})"),
+                None => format!("{entity:?}"),
+            };
+            egui::CollapsingHeader::new(title)
+                .id_source(entity)
+                .default_open(true)
+                .show(ui, |ui| {
+                    entity_ui(world, entity, ui);
+                });
+        }
+    }
+}
+
+fn entity_ui(world: &mut World, entity: Entity, ui: &mut egui::Ui) {
+    let controller = world
+        .get::<Controller>(entity)
+        .map_or("None", |controller| controller.label());
+    ui.label(format!("Controller: {controller}"));
+
+    if let Some(input_map) = world.get::<InputMap<CharacterControl>>(entity) {
+        egui::CollapsingHeader::new("Bindings")
+            .id_source((entity, "bindings"))
+            .show(ui, |ui| {
+                for (action, inputs) in describe_bindings(input_map) {
+                    ui.label(format!("{action:?}"));
+                    ui.indent((entity, action), |ui| {
+                        for input in inputs {
+                            ui.label(input);
+                        }
+                    });
+                }
+            });
+    }
+
+    if let Some(sample) = world.resource::<InputSamples>().0.get(&entity) {
+        ui.label(format!("Pressed: {:?}", sample.pressed));
+        ui.label(format!(
+            "Move axis: ({:.2}, {:.2})",
+            sample.axis.x, sample.axis.y
+        ));
+        for contribution in sample.contributions.iter() {
+            ui.label(format!(
+                "{:?}: ({:.2}, {:.2})",
+                contribution.source, contribution.direction.x, contribution.direction.y
+            ));
+        }
+        match sample.resolved {
+            Some(resolved) => ui.label(format!(
+                "Resolved movement: {:?} ({:.2}, {:.2})",
+                resolved.source, resolved.direction.x, resolved.direction.y
+            )),
+            None => ui.label("Resolved movement: none"),
+        };
+    }
+
+    ui.separator();
+    let mut virtual_input = world
+        .get::<VirtualInput>(entity)
+        .copied()
+        .unwrap_or_default();
+    let before = (virtual_input.movement, virtual_input.fire);
+
+    let mut hold = virtual_input.movement.is_some();
+    ui.checkbox(&mut hold, "Hold virtual movement");
+    let mut movement = virtual_input.movement.unwrap_or_default();
+    ui.add_enabled_ui(hold, |ui| {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut movement.x)
+                    .speed(0.05)
+                    .clamp_range(-1. ..=1.)
+                    .prefix("x: "),
+            );
+            ui.add(
+                egui::DragValue::new(&mut movement.y)
+                    .speed(0.05)
+                    .clamp_range(-1. ..=1.)
+                    .prefix("y: "),
+            );
+        });
+        // Buttons for the eight directions and standing still, laid out like a keypad
+        egui::Grid::new((entity, "directions")).show(ui, |ui| {
+            for y in [1., 0., -1.] {
+                for x in [-1., 0., 1.] {
+                    let direction = Vec2::new(x, y).normalize_or_zero();
+                    let label = match (x as i32, y as i32) {
+                        (-1, 1) => "↖",
+                        (0, 1) => "↑",
+                        (1, 1) => "↗",
+                        (-1, 0) => "←",
+                        (0, 0) => "·",
+                        (1, 0) => "→",
+                        (-1, -1) => "↙",
+                        (0, -1) => "↓",
+                        _ => "↘",
+                    };
+                    if ui.button(label).clicked() {
+                        movement = direction;
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    });
+    virtual_input.movement = hold.then_some(movement);
+    if ui.button("Tap fire").clicked() {
+        virtual_input.fire = true;
+    }
+
+    if (virtual_input.movement, virtual_input.fire) != before {
+        world.entity_mut(entity).insert(virtual_input);
+    }
+}
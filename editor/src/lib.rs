@@ -1,7 +1,8 @@
-use std::time::Duration;
+#![allow(clippy::type_complexity)]
 
 use bevy::{
     diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    log::LogPlugin,
     prelude::*,
 };
 use bevy_editor_pls::{
@@ -9,89 +10,90 @@ use bevy_editor_pls::{
     editor::Editor,
     prelude::*,
 };
-use bevy_game::{blueprints::CharacterBundle, controls::Controller};
+use bevy_game::blueprints::BlueprintRegistry;
+
+mod blueprints;
+mod camera;
+mod frames;
+mod history;
+mod input;
+mod paint;
+mod play;
+mod scene;
+mod sets;
+mod states;
+mod time;
+
+pub use frames::{FrameLimitPlugin, FrameLimitStatus};
 
 pub enum EditorOpenSetting {
     Windowed,
     FullScreen,
 }
 
-pub fn app_with_editor(editor_settings: EditorOpenSetting) -> App {
+// `log_filter` is added to the default log filter, such as `bevy_game=debug,bevy_ecs=warn`
+pub fn app_with_editor(editor_settings: EditorOpenSetting, log_filter: Option<&str>) -> App {
     let mut app = App::new();
+    let log_plugin = match log_filter {
+        Some(filter) => LogPlugin {
+            filter: format!("{},{filter}", LogPlugin::default().filter),
+            ..Default::default()
+        },
+        None => LogPlugin::default(),
+    };
     let editor_plugin = match editor_settings {
         EditorOpenSetting::FullScreen => EditorPlugin::default().on_second_monitor_fullscreen(),
         EditorOpenSetting::Windowed => EditorPlugin::default().in_new_window(Window::default()),
     };
     app.add_plugins((
-        DefaultPlugins,
+        DefaultPlugins.set(log_plugin),
         editor_plugin,
         FrameTimeDiagnosticsPlugin::default(),
         EntityCountDiagnosticsPlugin::default(),
         bevy_game::GamePlugin,
+        time::TimeControlsPlugin,
+        paint::PaintPlugin,
+        history::HistoryPlugin,
+        input::InputInspectorPlugin,
+        play::PlayInEditorPlugin,
+        camera::EditorCameraPlugin,
+        sets::SystemSetTogglesPlugin,
+        states::StateHistoryPlugin,
     ))
-    .add_systems(Update, (handle_pause, propagate_window_despawn));
+    .add_editor_window::<scene::LevelSceneWindow>()
+    .add_editor_window::<blueprints::SpawnWindow>()
+    .add_editor_window::<time::TimeWindow>()
+    .add_editor_window::<paint::PaintWindow>()
+    .add_editor_window::<history::HistoryWindow>()
+    .add_editor_window::<input::InputWindow>()
+    .add_editor_window::<play::PlayWindow>()
+    .add_editor_window::<camera::CameraWindow>()
+    .add_editor_window::<sets::SystemSetsWindow>()
+    .add_editor_window::<states::StatesWindow>()
+    .add_systems(Update, propagate_window_despawn);
 
     register_blueprints(&mut app.world);
 
     app
 }
 
-// Use this to register blueprints to the editor
+// Lists every blueprint that game crates registered to the `BlueprintRegistry` in the editor
 fn register_blueprints(world: &mut World) {
+    let blueprints: Vec<_> = world
+        .get_resource::<BlueprintRegistry>()
+        .map(|registry| registry.iter().cloned().collect())
+        .unwrap_or_default();
     let mut editor = world
         .get_resource_mut::<Editor>()
         .expect("Editor should exist");
     let state = editor
         .window_state_mut::<AddWindow>()
         .expect("AddWindow should exist");
-    state.add("Blueprints", AddItem::bundle::<CharacterBundle>());
-    state.add("Blueprints", AddItem::component::<Controller>());
-}
-
-fn handle_pause(
-    mut commands: Commands,
-    inputs: Res<Input<KeyCode>>,
-    mut fixed_time: ResMut<Time<Fixed>>,
-    mut pause_ui_entity: Local<Option<(Duration, Entity)>>,
-) {
-    // TODO: only if selecting the correct window?
-    if inputs.just_pressed(KeyCode::Escape) {
-        if let Some((prev_duration, entity)) = pause_ui_entity.take() {
-            commands.entity(entity).despawn_recursive();
-            fixed_time.set_timestep(prev_duration);
-        } else {
-            *pause_ui_entity = Some((
-                fixed_time.timestep(),
-                commands
-                    .spawn(NodeBundle {
-                        style: Style {
-                            position_type: PositionType::Absolute,
-                            align_items: AlignItems::Center,
-                            justify_content: JustifyContent::Center,
-                            left: Val::Percent(35.),
-                            right: Val::Percent(35.),
-                            top: Val::Percent(10.),
-                            bottom: Val::Percent(70.),
-                            ..Default::default()
-                        },
-                        background_color: Color::DARK_GRAY.into(),
-                        border_color: Color::BLACK.into(),
-                        ..Default::default()
-                    })
-                    .with_children(|builder| {
-                        builder.spawn(TextBundle::from_section(
-                            "PAUSED",
-                            TextStyle {
-                                font_size: 64.,
-                                color: Color::WHITE,
-                                ..Default::default()
-                            },
-                        ));
-                    })
-                    .id(),
-            ));
-            fixed_time.set_timestep(Duration::MAX);
-        }
+    for blueprint in blueprints {
+        state.add(
+            blueprint.category,
+            AddItem::new(blueprint.name.into(), blueprint.add_to_entity),
+        );
     }
 }
 
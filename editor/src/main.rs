@@ -1,19 +1,70 @@
-use editor::{app_with_editor, EditorOpenSetting};
+use std::process::ExitCode;
 
-use clap::Parser;
+use editor::{app_with_editor, EditorOpenSetting, FrameLimitPlugin, FrameLimitStatus};
+
+use bevy_game::{GameState, SceneLevel, StartState};
+use clap::{Parser, ValueEnum};
+
+#[derive(Clone, Copy, Debug)]
+#[derive(ValueEnum)]
+pub enum StartingState {
+    Playing,
+    Menu,
+}
+
+impl From<StartingState> for GameState {
+    fn from(state: StartingState) -> Self {
+        match state {
+            StartingState::Playing => GameState::Playing,
+            StartingState::Menu => GameState::Menu,
+        }
+    }
+}
 
 #[derive(Debug, Parser)]
 pub struct Arguments {
     #[arg(short, long, default_value_t = false)]
     pub fullscreen: bool,
+    /// A level scene to play as the first level, as an asset path such as `levels/editor.scn.ron`
+    #[arg(long)]
+    pub scene: Option<String>,
+    /// The state to enter once loading finishes, instead of the menu
+    #[arg(long, value_enum)]
+    pub state: Option<StartingState>,
+    /// Exits after this many frames, with status 0 if the game loaded and reached `--state`
+    #[arg(long)]
+    pub frames: Option<u32>,
+    /// A log filter added to the default one, such as `bevy_game=debug`
+    #[arg(long)]
+    pub log: Option<String>,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = Arguments::parse();
     let open_settings = match args.fullscreen {
         true => EditorOpenSetting::FullScreen,
         false => EditorOpenSetting::Windowed,
     };
 
-    app_with_editor(open_settings).run();
+    let mut app = app_with_editor(open_settings, args.log.as_deref());
+    if let Some(scene) = args.scene {
+        app.insert_resource(SceneLevel(scene));
+    }
+    if let Some(state) = args.state {
+        app.insert_resource(StartState(state.into()));
+    }
+    let status = FrameLimitStatus::default();
+    if let Some(frames) = args.frames {
+        app.add_plugins(FrameLimitPlugin {
+            frames,
+            expected_state: args.state.map(GameState::from),
+            status: status.clone(),
+        });
+    }
+    app.run();
+
+    match args.frames {
+        Some(_) => status.exit_code(),
+        None => ExitCode::SUCCESS,
+    }
 }
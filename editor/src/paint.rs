@@ -0,0 +1,366 @@
+use std::any::TypeId;
+
+use bevy::{
+    prelude::*,
+    render::camera::RenderTarget,
+    window::{PrimaryWindow, WindowRef},
+};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::blueprints::{
+    tile_cell, tile_position, Blueprint, BlueprintRegistry, SpawnBlueprint, Terrain, Wall,
+    TILE_SIZE,
+};
+
+// How many cells of the grid are drawn around the cursor in each direction
+const GRID_RADIUS: i32 = 3;
+
+pub struct PaintPlugin;
+
+/// This plugin paints tiles and places blueprints with the mouse in the game window,
+/// using the layer, tool and brush picked in the "Paint" window
+impl Plugin for PaintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PaintTool>().add_systems(
+            Update,
+            (paint_level, draw_paint_cursor)
+                .chain()
+                .run_if(|tool: Res<PaintTool>| tool.enabled),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaintLayer {
+    #[default]
+    Terrain,
+    Collision,
+    Entities,
+}
+
+impl PaintLayer {
+    const ALL: [PaintLayer; 3] = [
+        PaintLayer::Terrain,
+        PaintLayer::Collision,
+        PaintLayer::Entities,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            PaintLayer::Terrain => "Terrain",
+            PaintLayer::Collision => "Collision",
+            PaintLayer::Entities => "Entities",
+        }
+    }
+
+    // Terrain and collision tiles are the blueprints of their category,
+    // and any other blueprint with a position is an entity
+    fn contains(&self, blueprint: &Blueprint) -> bool {
+        match self {
+            PaintLayer::Terrain => blueprint.category == Terrain::CATEGORY,
+            PaintLayer::Collision => blueprint.category == Wall::CATEGORY,
+            PaintLayer::Entities => {
+                blueprint.category != Terrain::CATEGORY
+                    && blueprint.category != Wall::CATEGORY
+                    && blueprint.components().contains(&TypeId::of::<Transform>())
+            }
+        }
+    }
+
+    // Tiles always snap to the grid, and replace the tile of the same layer under them
+    fn has_tiles(&self) -> bool {
+        !matches!(self, PaintLayer::Entities)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaintMode {
+    #[default]
+    Paint,
+    // Fills the box between the cells where the mouse was pressed and released
+    BoxFill,
+    Erase,
+    // Picks the blueprint under the mouse as the brush
+    Pick,
+}
+
+impl PaintMode {
+    const ALL: [PaintMode; 4] = [
+        PaintMode::Paint,
+        PaintMode::BoxFill,
+        PaintMode::Erase,
+        PaintMode::Pick,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            PaintMode::Paint => "Paint",
+            PaintMode::BoxFill => "Box fill",
+            PaintMode::Erase => "Erase",
+            PaintMode::Pick => "Pick",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            PaintMode::Paint => Color::WHITE,
+            PaintMode::BoxFill => Color::CYAN,
+            PaintMode::Erase => Color::RED,
+            PaintMode::Pick => Color::YELLOW,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(Resource)]
+pub struct PaintTool {
+    pub enabled: bool,
+    pub layer: PaintLayer,
+    pub mode: PaintMode,
+    // The name of the blueprint to paint
+    pub brush: Option<&'static str>,
+    // Whether entities snap to the grid
+    pub snap: bool,
+    box_start: Option<IVec2>,
+    last_painted: Option<IVec2>,
+}
+
+impl Default for PaintTool {
+    fn default() -> Self {
+        PaintTool {
+            enabled: false,
+            layer: PaintLayer::default(),
+            mode: PaintMode::default(),
+            brush: None,
+            snap: true,
+            box_start: None,
+            last_painted: None,
+        }
+    }
+}
+
+// The cursor position in the world, if the cursor is over the game window
+fn cursor_world_position(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+) -> Option<Vec2> {
+    let cursor = window_query.get_single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = camera_query.iter().find(|(camera, _)| {
+        camera.is_active && matches!(camera.target, RenderTarget::Window(WindowRef::Primary))
+    })?;
+    camera.viewport_to_world_2d(camera_transform, cursor)
+}
+
+// Entities of the layer whose position is in the cell
+fn entities_at<'a>(
+    registry: &'a BlueprintRegistry,
+    entity_query: &Query<EntityRef>,
+    layer: PaintLayer,
+    cell: IVec2,
+) -> Vec<(Entity, &'a Blueprint)> {
+    entity_query
+        .iter()
+        .filter_map(|entity| {
+            let transform = entity.get::<Transform>()?;
+            if tile_cell(transform.translation.xy()) != cell {
+                return None;
+            }
+            let blueprint = registry.find(&entity)?;
+            layer
+                .contains(blueprint)
+                .then_some((entity.id(), blueprint))
+        })
+        .collect()
+}
+
+fn erase(
+    commands: &mut Commands,
+    registry: &BlueprintRegistry,
+    entity_query: &Query<EntityRef>,
+    layer: PaintLayer,
+    cell: IVec2,
+) {
+    for (entity, _) in entities_at(registry, entity_query, layer, cell) {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn paint_level(
+    mut commands: Commands,
+    mut tool: ResMut<PaintTool>,
+    mouse: Res<Input<MouseButton>>,
+    registry: Res<BlueprintRegistry>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    entity_query: Query<EntityRef>,
+) {
+    let Some(position) = cursor_world_position(&window_query, &camera_query) else {
+        tool.box_start = None;
+        tool.last_painted = None;
+        return;
+    };
+    let cell = tile_cell(position);
+    let (layer, snap) = (tool.layer, tool.snap);
+
+    let paint = |commands: &mut Commands, brush: &'static str, cell: IVec2| {
+        let position = if layer.has_tiles() || snap {
+            tile_position(cell)
+        } else {
+            position
+        };
+        if layer.has_tiles() {
+            erase(commands, &registry, &entity_query, layer, cell);
+        }
+        commands.add(SpawnBlueprint::new(brush).at(position));
+    };
+
+    // Right click erases whatever the tool is
+    if mouse.pressed(MouseButton::Right) {
+        erase(&mut commands, &registry, &entity_query, layer, cell);
+        return;
+    }
+
+    match tool.mode {
+        PaintMode::Paint => {
+            let Some(brush) = tool.brush else {
+                return;
+            };
+            // Tiles are painted while dragging, entities once per click
+            let repaint = layer.has_tiles() && tool.last_painted != Some(cell);
+            if mouse.just_pressed(MouseButton::Left)
+                || (mouse.pressed(MouseButton::Left) && repaint)
+            {
+                paint(&mut commands, brush, cell);
+                tool.last_painted = Some(cell);
+            }
+            if !mouse.pressed(MouseButton::Left) {
+                tool.last_painted = None;
+            }
+        }
+        PaintMode::BoxFill => {
+            if mouse.just_pressed(MouseButton::Left) {
+                tool.box_start = Some(cell);
+            }
+            if mouse.just_released(MouseButton::Left) {
+                if let (Some(start), Some(brush)) = (tool.box_start.take(), tool.brush) {
+                    let (min, max) = (start.min(cell), start.max(cell));
+                    for x in min.x..=max.x {
+                        for y in min.y..=max.y {
+                            paint(&mut commands, brush, IVec2::new(x, y));
+                        }
+                    }
+                }
+            }
+        }
+        PaintMode::Erase => {
+            if mouse.pressed(MouseButton::Left) {
+                erase(&mut commands, &registry, &entity_query, layer, cell);
+            }
+        }
+        PaintMode::Pick => {
+            if mouse.just_pressed(MouseButton::Left) {
+                if let Some((_, blueprint)) =
+                    entities_at(&registry, &entity_query, layer, cell).first()
+                {
+                    tool.brush = Some(blueprint.name);
+                    tool.mode = PaintMode::Paint;
+                }
+            }
+        }
+    }
+}
+
+fn draw_paint_cursor(
+    mut gizmos: Gizmos,
+    tool: Res<PaintTool>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+) {
+    let Some(position) = cursor_world_position(&window_query, &camera_query) else {
+        return;
+    };
+    let cell = tile_cell(position);
+    for x in -GRID_RADIUS..=GRID_RADIUS {
+        for y in -GRID_RADIUS..=GRID_RADIUS {
+            gizmos.rect_2d(
+                tile_position(cell + IVec2::new(x, y)),
+                0.,
+                Vec2::splat(TILE_SIZE),
+                Color::rgba(1., 1., 1., 0.1),
+            );
+        }
+    }
+
+    let start = tool.box_start.unwrap_or(cell);
+    let (min, max) = (start.min(cell), start.max(cell));
+    gizmos.rect_2d(
+        (tile_position(min) + tile_position(max)) / 2.,
+        0.,
+        (max - min + IVec2::ONE).as_vec2() * TILE_SIZE,
+        tool.mode.color(),
+    );
+    if !tool.layer.has_tiles() && !tool.snap {
+        gizmos.circle_2d(position, 8., tool.mode.color());
+    }
+}
+
+pub struct PaintWindow;
+
+impl EditorWindow for PaintWindow {
+    type State = ();
+    const NAME: &'static str = "Paint";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        world.resource_scope(|world, mut tool: Mut<PaintTool>| {
+            let registry = world.resource::<BlueprintRegistry>();
+
+            ui.checkbox(&mut tool.enabled, "Paint in the game window");
+            ui.horizontal(|ui| {
+                ui.label("Layer");
+                for layer in PaintLayer::ALL {
+                    if ui
+                        .selectable_label(tool.layer == layer, layer.label())
+                        .clicked()
+                    {
+                        tool.layer = layer;
+                        tool.brush = None;
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Tool");
+                for mode in PaintMode::ALL {
+                    if ui
+                        .selectable_label(tool.mode == mode, mode.label())
+                        .clicked()
+                    {
+                        tool.mode = mode;
+                        tool.box_start = None;
+                    }
+                }
+            });
+            if !tool.layer.has_tiles() {
+                ui.checkbox(&mut tool.snap, "Snap to grid");
+            }
+
+            ui.separator();
+            let layer = tool.layer;
+            for blueprint in registry
+                .iter()
+                .filter(|blueprint| layer.contains(blueprint))
+            {
+                if ui
+                    .selectable_label(tool.brush == Some(blueprint.name), blueprint.name)
+                    .clicked()
+                {
+                    tool.brush = Some(blueprint.name);
+                }
+            }
+
+            ui.separator();
+            ui.label("Left click uses the tool and right click erases");
+        });
+    }
+}
@@ -0,0 +1,180 @@
+use bevy::{prelude::*, transform::TransformSystem};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::{
+    blueprints::Projectile,
+    gameplay::{LevelStats, LevelTimer},
+    restore_level, snapshot_level, GameState,
+};
+
+use crate::history::EditorHistory;
+
+const PLAY_KEY: KeyCode = KeyCode::F5;
+
+pub struct PlayInEditorPlugin;
+
+/// This plugin lets the editor play the level being edited and go back to it afterwards:
+/// Play snapshots the level and enters `GameState::Playing`, and Stop restores the snapshot
+impl Plugin for PlayInEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlaySession>()
+            .add_systems(Update, handle_play_key)
+            .add_systems(
+                PostUpdate,
+                // After the scene spawner, which may spawn a scene level on entering `GameState::Playing`
+                restore_snapshot
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(|session: Res<PlaySession>| session.stopping),
+            );
+    }
+}
+
+// Everything Stop puts back the way it was when Play was pressed
+struct PlaySnapshot {
+    level: DynamicScene,
+    timer: Option<LevelTimer>,
+    stats: Option<LevelStats>,
+    state: GameState,
+    paused: bool,
+}
+
+#[derive(Default)]
+#[derive(Resource)]
+pub struct PlaySession {
+    snapshot: Option<PlaySnapshot>,
+    // The snapshot is restored once the game is back in the state it was taken in
+    stopping: bool,
+}
+
+impl PlaySession {
+    pub fn is_playing(&self) -> bool {
+        self.snapshot.is_some() && !self.stopping
+    }
+
+    pub fn play(&mut self, world: &mut World) {
+        if self.snapshot.is_some() {
+            return;
+        }
+        let state = *world.resource::<State<GameState>>().get();
+        if state == GameState::Loading {
+            warn!("Cannot play in the editor before the game finishes loading");
+            return;
+        }
+        self.snapshot = Some(PlaySnapshot {
+            level: snapshot_level(world),
+            timer: world.get_resource::<LevelTimer>().cloned(),
+            stats: world.get_resource::<LevelStats>().copied(),
+            state,
+            paused: world.resource::<Time<Virtual>>().is_paused(),
+        });
+        if state != GameState::Playing {
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::Playing);
+        }
+        world.resource_mut::<Time<Virtual>>().unpause();
+        info!("Playing in the editor");
+    }
+
+    pub fn stop(&mut self, world: &mut World) {
+        let Some(snapshot) = &self.snapshot else {
+            return;
+        };
+        if *world.resource::<State<GameState>>().get() != snapshot.state {
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(snapshot.state);
+        }
+        self.stopping = true;
+    }
+
+    pub fn toggle(&mut self, world: &mut World) {
+        if self.is_playing() {
+            self.stop(world);
+        } else {
+            self.play(world);
+        }
+    }
+}
+
+fn handle_play_key(world: &mut World) {
+    if world.resource::<Input<KeyCode>>().just_pressed(PLAY_KEY) {
+        world.resource_scope(|world, mut session: Mut<PlaySession>| session.toggle(world));
+    }
+}
+
+fn restore_snapshot(world: &mut World) {
+    world.resource_scope(|world, mut session: Mut<PlaySession>| {
+        let Some(snapshot) = &session.snapshot else {
+            session.stopping = false;
+            return;
+        };
+        // Wait for the transition back, so entering the state does not spawn over the restored level
+        if *world.resource::<State<GameState>>().get() != snapshot.state {
+            return;
+        }
+
+        // Projectiles are not part of the level, but should not outlive the session
+        let mut projectile_query = world.query_filtered::<Entity, With<Projectile>>();
+        let projectiles: Vec<Entity> = projectile_query.iter(world).collect();
+        for entity in projectiles {
+            world.entity_mut(entity).despawn_recursive();
+        }
+        if let Err(error) = restore_level(world, &snapshot.level) {
+            error!("Could not restore the level after playing: {error}");
+        }
+        match snapshot.timer.clone() {
+            Some(timer) => world.insert_resource(timer),
+            None => {
+                world.remove_resource::<LevelTimer>();
+            }
+        }
+        match snapshot.stats {
+            Some(stats) => world.insert_resource(stats),
+            None => {
+                world.remove_resource::<LevelStats>();
+            }
+        }
+        if snapshot.paused {
+            world.resource_mut::<Time<Virtual>>().pause();
+        }
+        // The restored entities replace the ones older edits refer to
+        world.resource_mut::<EditorHistory>().clear();
+
+        session.snapshot = None;
+        session.stopping = false;
+        info!("Stopped playing in the editor");
+    });
+}
+
+pub struct PlayWindow;
+
+impl EditorWindow for PlayWindow {
+    type State = ();
+    const NAME: &'static str = "Play";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        world.resource_scope(|world, mut session: Mut<PlaySession>| {
+            if session.is_playing() {
+                if ui.button("⏹ Stop").clicked() {
+                    session.stop(world);
+                }
+                ui.label("Stop restores the level as it was when Play was pressed");
+            } else if session.stopping {
+                ui.label("Restoring the level...");
+            } else {
+                if ui.button("▶ Play").clicked() {
+                    session.play(world);
+                }
+                ui.label("Play snapshots the level and enters the Playing state");
+            }
+            ui.label(format!(
+                "State: {:?}",
+                world.resource::<State<GameState>>().get()
+            ));
+            ui.label("F5 plays and stops from either window");
+        });
+    }
+}
@@ -0,0 +1,127 @@
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::{gameplay::LevelObjective, load_level_scene, save_level_scene};
+
+// An asset path, so the editor loads this file with `cargo run -- --scene levels/editor.scn.ron`
+const DEFAULT_SCENE_PATH: &str = "levels/editor.scn.ron";
+
+// Saves and loads the level entities of the world, along with the level objective
+pub struct LevelSceneWindow;
+
+pub struct LevelSceneWindowState {
+    path: String,
+    objective: LevelObjective,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for LevelSceneWindowState {
+    fn default() -> Self {
+        LevelSceneWindowState {
+            path: DEFAULT_SCENE_PATH.to_string(),
+            objective: LevelObjective::ReachExit,
+            status: None,
+        }
+    }
+}
+
+impl EditorWindow for LevelSceneWindow {
+    type State = LevelSceneWindowState;
+    const NAME: &'static str = "Level Scene";
+
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let state = cx
+            .state_mut::<LevelSceneWindow>()
+            .expect("LevelSceneWindow state should exist");
+
+        ui.horizontal(|ui| {
+            ui.label("Asset path");
+            ui.text_edit_singleline(&mut state.path);
+        });
+        objective_ui(ui, &mut state.objective);
+
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                world.insert_resource(state.objective);
+                state.status = Some(
+                    save_level_scene(world, &scene_file(&state.path))
+                        .map(|_| format!("Saved {}", state.path))
+                        .map_err(|error| error.to_string()),
+                );
+            }
+            if ui.button("Load").clicked() {
+                state.status = Some(
+                    load_level_scene(world, &scene_file(&state.path))
+                        .map(|_| format!("Loaded {}", state.path))
+                        .map_err(|error| error.to_string()),
+                );
+                if let Some(objective) = world.get_resource::<LevelObjective>() {
+                    state.objective = *objective;
+                }
+            }
+        });
+        match &state.status {
+            Some(Ok(message)) => {
+                ui.label(message);
+            }
+            Some(Err(error)) => {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            None => {}
+        }
+    }
+}
+
+// The file an asset path refers to, in the same assets folder the asset server reads,
+// so saving and loading do not depend on the directory the editor was launched from
+fn scene_file(path: &str) -> PathBuf {
+    FileAssetReader::get_base_path()
+        .join(AssetPlugin::default().file_path)
+        .join(path)
+}
+
+fn objective_ui(ui: &mut egui::Ui, objective: &mut LevelObjective) {
+    let label = match objective {
+        LevelObjective::ReachExit => "Reach the exit",
+        LevelObjective::CollectItems(_) => "Collect items",
+        LevelObjective::Survive(_) => "Survive",
+    };
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label("Objective")
+            .selected_text(label)
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(false, "Reach the exit").clicked() {
+                    *objective = LevelObjective::ReachExit;
+                }
+                if ui.selectable_label(false, "Collect items").clicked() {
+                    *objective = LevelObjective::CollectItems(1);
+                }
+                if ui.selectable_label(false, "Survive").clicked() {
+                    *objective = LevelObjective::Survive(Duration::from_secs(30));
+                }
+            });
+        match objective {
+            LevelObjective::ReachExit => {}
+            LevelObjective::CollectItems(count) => {
+                ui.add(egui::DragValue::new(count).suffix(" items"));
+            }
+            LevelObjective::Survive(duration) => {
+                let mut seconds = duration.as_secs_f32();
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut seconds)
+                            .clamp_range(1. ..=3600.)
+                            .suffix(" s"),
+                    )
+                    .changed()
+                {
+                    *duration = Duration::from_secs_f32(seconds);
+                }
+            }
+        }
+    });
+}
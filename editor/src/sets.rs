@@ -0,0 +1,340 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::schedule::{InternedSystemSet, NodeId, ScheduleLabel},
+    prelude::*,
+    utils::{
+        petgraph::{algo::toposort, Direction},
+        HashSet, Instant,
+    },
+};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::{
+    controls::{ControllerSet, ControllerSystem},
+    flavor::{
+        audio::{AudioSet, AudioSystem},
+        graphics::{GraphicsSet, GraphicsSystem},
+    },
+    gameplay::{GameplaySet, GameplaySystem},
+    network::{NetworkSet, NetworkSystem},
+};
+
+pub struct SystemSetTogglesPlugin;
+
+/// This plugin lets the editor turn the game's system sets, and the sets of their parts, on and off at runtime,
+/// and measures how long each of them took in the last frame
+impl Plugin for SystemSetTogglesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SystemSetToggles>();
+        for (set, parts) in [
+            (
+                ControllerSet.intern(),
+                vec![
+                    ControllerSystem::Sync.intern(),
+                    ControllerSystem::Input.intern(),
+                    ControllerSystem::Touch.intern(),
+                ],
+            ),
+            (
+                NetworkSet.intern(),
+                vec![
+                    NetworkSystem::Client.intern(),
+                    NetworkSystem::Server.intern(),
+                    NetworkSystem::Rollback.intern(),
+                ],
+            ),
+            (
+                AudioSet.intern(),
+                vec![
+                    AudioSystem::Config.intern(),
+                    AudioSystem::Movement.intern(),
+                    AudioSystem::Effects.intern(),
+                ],
+            ),
+            (
+                GraphicsSet.intern(),
+                vec![
+                    GraphicsSystem::Characters.intern(),
+                    GraphicsSystem::Level.intern(),
+                    GraphicsSystem::Effects.intern(),
+                    GraphicsSystem::Gizmos.intern(),
+                ],
+            ),
+            (
+                GameplaySet.intern(),
+                vec![
+                    GameplaySystem::Movement.intern(),
+                    GameplaySystem::Walls.intern(),
+                    GameplaySystem::Projectiles.intern(),
+                    GameplaySystem::Pickups.intern(),
+                    GameplaySystem::Objectives.intern(),
+                ],
+            ),
+        ] {
+            // The controller systems read input in `PreUpdate`, and everything else runs in `Update`
+            for schedule in [PreUpdate.intern(), Update.intern()] {
+                add_toggled_set(app, schedule, set, None);
+                for part in parts.iter() {
+                    add_toggled_set(app, schedule, *part, Some(set));
+                }
+            }
+        }
+        app.add_systems(Startup, describe_sets);
+    }
+}
+
+// How a set runs in one schedule
+#[derive(Debug)]
+pub struct SetSchedule {
+    pub schedule: String,
+    // The systems of the set, in an order they can run in
+    pub systems: Vec<String>,
+    // The time from the first system of the set starting to the last one finishing,
+    // including any other system that ran in parallel
+    pub last_run: Option<Duration>,
+    started: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub struct ToggledSet {
+    pub name: String,
+    pub enabled: bool,
+    pub schedules: Vec<SetSchedule>,
+    set: InternedSystemSet,
+    // The set this is a part of, which turns it off as well
+    parent: Option<InternedSystemSet>,
+}
+
+impl ToggledSet {
+    fn schedule_mut(&mut self, schedule: &str) -> Option<&mut SetSchedule> {
+        self.schedules
+            .iter_mut()
+            .find(|set_schedule| set_schedule.schedule == schedule)
+    }
+}
+
+#[derive(Debug, Default)]
+#[derive(Resource)]
+pub struct SystemSetToggles {
+    // The sets and their parts, in the order they run in `Update`
+    pub sets: Vec<ToggledSet>,
+}
+
+impl SystemSetToggles {
+    fn get_mut(&mut self, set: InternedSystemSet) -> Option<&mut ToggledSet> {
+        self.sets.iter_mut().find(|toggled| toggled.set == set)
+    }
+
+    fn is_enabled(&self, set: InternedSystemSet) -> bool {
+        !self
+            .sets
+            .iter()
+            .any(|toggled| toggled.set == set && !toggled.enabled)
+    }
+}
+
+// Adds a run condition to the set, and systems that time it from right before to right after it
+fn add_toggled_set(
+    app: &mut App,
+    schedule: impl ScheduleLabel + Clone,
+    set: InternedSystemSet,
+    parent: Option<InternedSystemSet>,
+) {
+    let schedule_name = format!("{schedule:?}");
+    let mut toggles = app.world.resource_mut::<SystemSetToggles>();
+    if toggles.get_mut(set).is_none() {
+        toggles.sets.push(ToggledSet {
+            name: format!("{set:?}"),
+            enabled: true,
+            schedules: Vec::new(),
+            set,
+            parent,
+        });
+    }
+    toggles
+        .get_mut(set)
+        .expect("The set was just added")
+        .schedules
+        .push(SetSchedule {
+            schedule: schedule_name.clone(),
+            systems: Vec::new(),
+            last_run: None,
+            started: None,
+        });
+
+    let start_name = schedule_name.clone();
+    app.configure_sets(
+        schedule.clone(),
+        set.run_if(move |toggles: Res<SystemSetToggles>| toggles.is_enabled(set)),
+    )
+    .add_systems(
+        schedule,
+        (
+            (move |mut toggles: ResMut<SystemSetToggles>| {
+                if let Some(set_schedule) = toggles
+                    .get_mut(set)
+                    .and_then(|toggled| toggled.schedule_mut(&start_name))
+                {
+                    set_schedule.started = Some(Instant::now());
+                }
+            })
+            .before(set),
+            (move |mut toggles: ResMut<SystemSetToggles>| {
+                if let Some(set_schedule) = toggles
+                    .get_mut(set)
+                    .and_then(|toggled| toggled.schedule_mut(&schedule_name))
+                {
+                    set_schedule.last_run =
+                        set_schedule.started.take().map(|start| start.elapsed());
+                }
+            })
+            .after(set),
+        ),
+    );
+}
+
+// Module paths make the list hard to read, and closures are named after the function defining them
+fn system_name(name: &str) -> String {
+    let path = name.split('<').next().unwrap_or(name);
+    let mut segments = path.rsplit("::");
+    match segments.next() {
+        Some("{{closure}}") => format!("{} (closure)", segments.next().unwrap_or_default()),
+        Some(segment) => segment.to_string(),
+        None => name.to_string(),
+    }
+}
+
+// Reads the systems of each set and the order of the sets from the schedules
+// Building a schedule moves its systems out of its graph, so this runs at startup,
+// before `PreUpdate` and `Update` run for the first time
+fn describe_sets(world: &mut World) {
+    world.resource_scope(|world, mut toggles: Mut<SystemSetToggles>| {
+        let schedules = world.resource::<Schedules>();
+        for (label, schedule) in schedules.iter() {
+            let schedule_name = format!("{label:?}");
+            let graph = schedule.graph();
+            // A schedule with a cycle fails to build with a better error than this could give
+            let order = toposort(graph.dependency().graph(), None).unwrap_or_default();
+            for toggled in toggles.sets.iter_mut() {
+                let Some(set_node) = graph
+                    .system_sets()
+                    .find(|(_, set, _)| **set == *toggled.set)
+                    .map(|(node, ..)| node)
+                else {
+                    continue;
+                };
+                let Some(set_schedule) = toggled.schedule_mut(&schedule_name) else {
+                    continue;
+                };
+
+                // Systems can be in anonymous sets nested in the set, such as ones that share a run condition
+                let mut systems = HashSet::<NodeId>::new();
+                let mut nested_sets = vec![set_node];
+                while let Some(node) = nested_sets.pop() {
+                    for child in graph
+                        .hierarchy()
+                        .graph()
+                        .neighbors_directed(node, Direction::Outgoing)
+                    {
+                        if child.is_system() {
+                            systems.insert(child);
+                        } else {
+                            nested_sets.push(child);
+                        }
+                    }
+                }
+                set_schedule.systems = order
+                    .iter()
+                    .filter(|node| systems.contains(*node))
+                    .filter_map(|node| graph.get_system_at(*node))
+                    .map(|system| system_name(&system.name()))
+                    .collect();
+            }
+
+            if *label == *Update.intern() {
+                let position = |set: InternedSystemSet| {
+                    graph
+                        .system_sets()
+                        .find(|(_, other, _)| **other == *set)
+                        .and_then(|(node, ..)| order.iter().position(|other| *other == node))
+                };
+                toggles
+                    .sets
+                    .sort_by_key(|toggled| position(toggled.set).unwrap_or(usize::MAX));
+            }
+        }
+    });
+}
+
+pub struct SystemSetsWindow;
+
+impl EditorWindow for SystemSetsWindow {
+    type State = ();
+    const NAME: &'static str = "System Sets";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let mut toggles = world.resource_mut::<SystemSetToggles>();
+        if ui.button("Enable all").clicked() {
+            for toggled in toggles.sets.iter_mut() {
+                toggled.enabled = true;
+            }
+        }
+        ui.separator();
+
+        let sets = &mut toggles.sets;
+        let parents: Vec<usize> = (0..sets.len())
+            .filter(|index| sets[*index].parent.is_none())
+            .collect();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (number, index) in parents.into_iter().enumerate() {
+                let parent = &mut sets[index];
+                ui.checkbox(
+                    &mut parent.enabled,
+                    format!("{}. {}", number + 1, parent.name),
+                );
+                let (set, enabled) = (parent.set, parent.enabled);
+                // Parts of different sets can have the same name, so the sets themselves identify them
+                ui.indent(set, |ui| {
+                    schedules_ui(ui, &sets[index], enabled);
+                    for part in sets.iter_mut().filter(|part| part.parent == Some(set)) {
+                        ui.add_enabled_ui(enabled, |ui| {
+                            ui.checkbox(&mut part.enabled, &part.name);
+                        });
+                        let part_enabled = enabled && part.enabled;
+                        ui.indent(part.set, |ui| schedules_ui(ui, part, part_enabled));
+                    }
+                });
+            }
+        });
+    }
+}
+
+// The systems of a set and how long they took, in each schedule the set has systems in
+fn schedules_ui(ui: &mut egui::Ui, toggled: &ToggledSet, enabled: bool) {
+    // Sets are configured in every schedule, but only list the ones they have systems in
+    for set_schedule in toggled
+        .schedules
+        .iter()
+        .filter(|set_schedule| !set_schedule.systems.is_empty())
+    {
+        let last_run = match (enabled, set_schedule.last_run) {
+            (false, _) => "disabled".to_string(),
+            (true, Some(last_run)) => format!("{:.3} ms", last_run.as_secs_f64() * 1000.),
+            (true, None) => "not run yet".to_string(),
+        };
+        egui::CollapsingHeader::new(format!(
+            "{}: {} systems, {last_run}",
+            set_schedule.schedule,
+            set_schedule.systems.len(),
+        ))
+        .id_source((toggled.set, &set_schedule.schedule))
+        .show(ui, |ui| {
+            for system in set_schedule.systems.iter() {
+                ui.label(system);
+            }
+        });
+    }
+}
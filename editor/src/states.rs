@@ -0,0 +1,197 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::{core::FrameCount, ecs::schedule::apply_state_transition, prelude::*};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+use bevy_game::{flavor::loading::AssetLoadState, GameState, MenuLoadState};
+
+// Older transitions are dropped once the log is this long
+const MAX_TRANSITIONS: usize = 200;
+
+pub struct StateHistoryPlugin;
+
+/// This plugin logs every transition of `GameState`, `MenuLoadState` and `AssetLoadState`,
+/// and lets the "States" window force `GameState` into any state, including the one it is already in
+impl Plugin for StateHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StateLog>().add_systems(
+            StateTransition,
+            (
+                log_transitions::<GameState>.after(apply_state_transition::<GameState>),
+                log_transitions::<MenuLoadState>.after(apply_state_transition::<MenuLoadState>),
+                log_transitions::<AssetLoadState>.after(apply_state_transition::<AssetLoadState>),
+                reenter_game_state.after(log_transitions::<GameState>),
+            ),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Transition {
+    pub state: &'static str,
+    // None for the state each type starts in
+    pub from: Option<String>,
+    pub to: String,
+    pub forced: bool,
+    // Real time since startup, since virtual time may be paused
+    pub time: Duration,
+    pub frame: u32,
+}
+
+#[derive(Debug, Default)]
+#[derive(Resource)]
+pub struct StateLog {
+    // Oldest first
+    pub transitions: VecDeque<Transition>,
+    // A `GameState` to exit and enter again, applied after the regular transitions of the frame
+    reenter: Option<GameState>,
+    // The type and value of the state the next transition of that type was forced into
+    forced: Option<(&'static str, String)>,
+}
+
+impl StateLog {
+    // Sets the next state, and marks the transition into it as forced once it happens
+    pub fn force<S: States>(&mut self, next_state: &mut NextState<S>, state: S) {
+        self.forced = Some((state_name::<S>(), format!("{state:?}")));
+        next_state.set(state);
+    }
+
+    fn record(&mut self, transition: Transition) {
+        if self.transitions.len() == MAX_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(transition);
+    }
+}
+
+// The type name of a state without its module path, such as `GameState`
+fn state_name<S: States>() -> &'static str {
+    let name = std::any::type_name::<S>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn log_transitions<S: States>(
+    mut log: ResMut<StateLog>,
+    state: Res<State<S>>,
+    mut previous: Local<Option<S>>,
+    time: Res<Time<Real>>,
+    frame: Res<FrameCount>,
+) {
+    let current = state.get();
+    if previous.as_ref() == Some(current) {
+        return;
+    }
+    let to = format!("{current:?}");
+    // Any transition of the type settles a forced one, even if something else set another state first
+    let forced = match &log.forced {
+        Some((name, _)) if *name == state_name::<S>() => log
+            .forced
+            .take()
+            .is_some_and(|(_, forced_to)| forced_to == to),
+        _ => false,
+    };
+    log.record(Transition {
+        state: state_name::<S>(),
+        from: previous.as_ref().map(|previous| format!("{previous:?}")),
+        to,
+        forced,
+        time: time.elapsed(),
+        frame: frame.0,
+    });
+    *previous = Some(current.clone());
+}
+
+// Setting `NextState` to the current state does nothing, so re-entering runs the schedules of
+// the transition the same way `apply_state_transition` does
+fn reenter_game_state(world: &mut World) {
+    let Some(state) = world.resource_mut::<StateLog>().reenter.take() else {
+        return;
+    };
+    if *world.resource::<State<GameState>>().get() != state {
+        return;
+    }
+    world.try_run_schedule(OnExit(state)).ok();
+    world
+        .try_run_schedule(OnTransition {
+            from: state,
+            to: state,
+        })
+        .ok();
+    world.try_run_schedule(OnEnter(state)).ok();
+
+    let time = world.resource::<Time<Real>>().elapsed();
+    let frame = world.resource::<FrameCount>().0;
+    world.resource_mut::<StateLog>().record(Transition {
+        state: state_name::<GameState>(),
+        from: Some(format!("{state:?}")),
+        to: format!("{state:?}"),
+        forced: true,
+        time,
+        frame,
+    });
+}
+
+pub struct StatesWindow;
+
+impl EditorWindow for StatesWindow {
+    type State = ();
+    const NAME: &'static str = "States";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let game_state = *world.resource::<State<GameState>>().get();
+        ui.label(format!("GameState: {game_state:?}"));
+        ui.label(format!(
+            "MenuLoadState: {:?}",
+            world.resource::<State<MenuLoadState>>().get()
+        ));
+        ui.label(format!(
+            "AssetLoadState: {:?}",
+            world.resource::<State<AssetLoadState>>().get()
+        ));
+
+        ui.separator();
+        ui.label("Force GameState into");
+        let mut forced = None;
+        ui.horizontal_wrapped(|ui| {
+            for state in GameState::ALL {
+                if ui.button(format!("{state:?}")).clicked() {
+                    forced = Some(state);
+                }
+            }
+        });
+        ui.label("Forcing the current state exits and enters it again");
+        if let Some(state) = forced {
+            world.resource_scope(|world, mut log: Mut<StateLog>| {
+                if state == game_state {
+                    // Such as to spawn the level again
+                    log.reenter = Some(state);
+                } else {
+                    log.force(&mut world.resource_mut::<NextState<GameState>>(), state);
+                }
+            });
+        }
+
+        ui.separator();
+        let mut log = world.resource_mut::<StateLog>();
+        if ui.button("Clear").clicked() {
+            log.transitions.clear();
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            // Newest first
+            for transition in log.transitions.iter().rev() {
+                let text = format!(
+                    "{:>9.3}s  frame {:<6} {}: {} -> {}{}",
+                    transition.time.as_secs_f64(),
+                    transition.frame,
+                    transition.state,
+                    transition.from.as_deref().unwrap_or("(start)"),
+                    transition.to,
+                    if transition.forced { " (forced)" } else { "" },
+                );
+                ui.label(egui::RichText::new(text).monospace());
+            }
+        });
+    }
+}
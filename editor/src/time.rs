@@ -0,0 +1,222 @@
+use bevy::{prelude::*, time::TimeSystem};
+use bevy_editor_pls::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    egui,
+};
+
+// Not Escape, which leaves online games
+const PAUSE_KEY: KeyCode = KeyCode::Pause;
+const STEP_FRAME_KEY: KeyCode = KeyCode::F6;
+const STEP_TICK_KEY: KeyCode = KeyCode::F7;
+const SLOWER_KEY: KeyCode = KeyCode::BracketLeft;
+const FASTER_KEY: KeyCode = KeyCode::BracketRight;
+
+const MIN_SPEED: f32 = 0.125;
+const MAX_SPEED: f32 = 8.;
+
+pub struct TimeControlsPlugin;
+
+/// This plugin lets the editor pause virtual time, step it one frame or one fixed tick at a time,
+/// and change its speed, from the keyboard or from the "Time" window
+impl Plugin for TimeControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeControls>()
+            .add_systems(First, step_virtual_time.after(TimeSystem))
+            .add_systems(Update, (handle_time_keys, show_pause_ui).chain());
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeStep {
+    // Advances virtual time by one frame of real time, scaled by the relative speed
+    Frame,
+    // Advances virtual time just enough to run `FixedUpdate` once
+    FixedTick,
+}
+
+// A step requested while paused, applied at the start of the next frame
+#[derive(Debug, Default)]
+#[derive(Resource)]
+pub struct TimeControls {
+    step: Option<TimeStep>,
+}
+
+impl TimeControls {
+    // Pauses virtual time if it is running, then steps it
+    pub fn step(&mut self, virtual_time: &mut Time<Virtual>, step: TimeStep) {
+        virtual_time.pause();
+        self.step = Some(step);
+    }
+}
+
+fn toggle_pause(virtual_time: &mut Time<Virtual>) {
+    if virtual_time.is_paused() {
+        virtual_time.unpause();
+    } else {
+        virtual_time.pause();
+    }
+}
+
+fn set_speed(virtual_time: &mut Time<Virtual>, speed: f32) {
+    virtual_time.set_relative_speed(speed.clamp(MIN_SPEED, MAX_SPEED));
+}
+
+// Keyboard input is shared by every window, so these work when the editor window has focus
+fn handle_time_keys(
+    inputs: Res<Input<KeyCode>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut controls: ResMut<TimeControls>,
+) {
+    if inputs.just_pressed(PAUSE_KEY) {
+        toggle_pause(&mut virtual_time);
+    }
+    if inputs.just_pressed(STEP_FRAME_KEY) {
+        controls.step(&mut virtual_time, TimeStep::Frame);
+    }
+    if inputs.just_pressed(STEP_TICK_KEY) {
+        controls.step(&mut virtual_time, TimeStep::FixedTick);
+    }
+    if inputs.just_pressed(SLOWER_KEY) {
+        let speed = virtual_time.relative_speed() / 2.;
+        set_speed(&mut virtual_time, speed);
+    }
+    if inputs.just_pressed(FASTER_KEY) {
+        let speed = virtual_time.relative_speed() * 2.;
+        set_speed(&mut virtual_time, speed);
+    }
+}
+
+// Paused virtual time does not advance in `TimeSystem`, so a step advances it by hand afterwards
+// `FixedUpdate` accumulates the same delta, and `Update` systems see it through `Time`
+fn step_virtual_time(
+    mut controls: ResMut<TimeControls>,
+    mut time: ResMut<Time>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    fixed_time: Res<Time<Fixed>>,
+    real_time: Res<Time<Real>>,
+) {
+    let Some(step) = controls.step.take() else {
+        return;
+    };
+    if !virtual_time.is_paused() {
+        return;
+    }
+    let delta = match step {
+        TimeStep::Frame => real_time
+            .delta()
+            .min(virtual_time.max_delta())
+            .mul_f32(virtual_time.relative_speed()),
+        TimeStep::FixedTick => fixed_time.timestep() - fixed_time.overstep(),
+    };
+    virtual_time.advance_by(delta);
+    *time = virtual_time.as_generic();
+}
+
+fn show_pause_ui(
+    mut commands: Commands,
+    virtual_time: Res<Time<Virtual>>,
+    mut pause_ui_entity: Local<Option<Entity>>,
+) {
+    match (virtual_time.is_paused(), *pause_ui_entity) {
+        (false, Some(entity)) => {
+            commands.entity(entity).despawn_recursive();
+            *pause_ui_entity = None;
+        }
+        (true, None) => {
+            *pause_ui_entity = Some(
+                commands
+                    .spawn(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            left: Val::Percent(35.),
+                            right: Val::Percent(35.),
+                            top: Val::Percent(10.),
+                            bottom: Val::Percent(70.),
+                            ..Default::default()
+                        },
+                        background_color: Color::DARK_GRAY.into(),
+                        border_color: Color::BLACK.into(),
+                        ..Default::default()
+                    })
+                    .with_children(|builder| {
+                        builder.spawn(TextBundle::from_section(
+                            "PAUSED",
+                            TextStyle {
+                                font_size: 64.,
+                                color: Color::WHITE,
+                                ..Default::default()
+                            },
+                        ));
+                    })
+                    .id(),
+            );
+        }
+        _ => {}
+    }
+}
+
+pub struct TimeWindow;
+
+impl EditorWindow for TimeWindow {
+    type State = ();
+    const NAME: &'static str = "Time";
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let mut step = None;
+        {
+            let mut virtual_time = world.resource_mut::<Time<Virtual>>();
+            ui.horizontal(|ui| {
+                let label = if virtual_time.is_paused() {
+                    "Resume"
+                } else {
+                    "Pause"
+                };
+                if ui.button(label).clicked() {
+                    toggle_pause(&mut virtual_time);
+                }
+                if ui.button("Step frame").clicked() {
+                    step = Some(TimeStep::Frame);
+                }
+                if ui.button("Step tick").clicked() {
+                    step = Some(TimeStep::FixedTick);
+                }
+            });
+
+            let mut speed = virtual_time.relative_speed();
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::Slider::new(&mut speed, MIN_SPEED..=MAX_SPEED)
+                        .logarithmic(true)
+                        .text("Speed"),
+                );
+                for preset in [0.25, 0.5, 1., 2., 4.] {
+                    if ui.button(format!("{preset}x")).clicked() {
+                        speed = preset;
+                    }
+                }
+            });
+            if speed != virtual_time.relative_speed() {
+                set_speed(&mut virtual_time, speed);
+            }
+            ui.label(format!(
+                "Virtual time: {:.2} s",
+                virtual_time.elapsed_seconds()
+            ));
+        }
+
+        let fixed_time = world.resource::<Time<Fixed>>();
+        ui.label(format!(
+            "Fixed time: {:.2} s, {:.0} Hz",
+            fixed_time.elapsed_seconds(),
+            1. / fixed_time.timestep().as_secs_f64()
+        ));
+
+        if let Some(step) = step {
+            world.resource_scope(|world, mut controls: Mut<TimeControls>| {
+                controls.step(&mut world.resource_mut::<Time<Virtual>>(), step);
+            });
+        }
+    }
+}
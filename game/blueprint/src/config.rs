@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{MovementProfile, RonAssetLoader};
+
+pub struct GameConfigPlugin;
+
+/// This plugin loads the tuning values from `assets/config/game.config.ron` into the [`GameConfig`] resource
+/// The resource is replaced whenever the asset changes, so with the `file_watcher` feature
+/// (enabled by the `dev` feature) edits to the file apply while the game is running
+impl Plugin for GameConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<GameConfig>()
+            .register_asset_loader(RonAssetLoader::<GameConfig>::new(&["config.ron"]))
+            .init_resource::<GameConfig>()
+            .add_systems(Startup, load_game_config)
+            .add_systems(
+                PreUpdate,
+                apply_game_config.run_if(resource_exists::<GameConfigHandle>()),
+            );
+    }
+}
+
+// Every value has a default so the config file only needs to list what it overrides
+#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Asset, Resource, TypePath)]
+#[serde(default)]
+pub struct GameConfig {
+    pub movement: MovementProfile,
+    pub controls: ControlsConfig,
+    pub audio: AudioConfig,
+    pub ui: UiConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ControlsConfig {
+    // Touches closer than this to the character do not move it
+    pub follow_epsilon: f32,
+    // Touches this far from the character move it at full speed
+    pub touch_radius: f32,
+    // Movement axis magnitudes below this are treated as zero, values of 1 or more are lowered to 0.95
+    pub dead_zone: f32,
+    // The magnitude left after the dead zone is raised to this power,
+    // so values above 1 give finer control near the center of the stick, values below 0.1 are raised to 0.1
+    pub response_exponent: f32,
+}
+
+impl Default for ControlsConfig {
+    fn default() -> Self {
+        ControlsConfig {
+            follow_epsilon: 5.,
+            touch_radius: 150.,
+            dead_zone: 0.15,
+            response_exponent: 1.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AudioConfig {
+    pub movement_volume: f64,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        AudioConfig {
+            movement_volume: 0.3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    pub button: ButtonConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ButtonConfig {
+    pub normal: Color,
+    pub hovered: Color,
+    pub active: Color,
+}
+
+impl Default for ButtonConfig {
+    fn default() -> Self {
+        ButtonConfig {
+            normal: Color::rgb(0.15, 0.15, 0.15),
+            hovered: Color::rgb(0.25, 0.25, 0.25),
+            active: Color::rgb(0.30, 0.30, 0.30),
+        }
+    }
+}
+
+#[derive(Resource)]
+struct GameConfigHandle(Handle<GameConfig>);
+
+fn load_game_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameConfigHandle(
+        asset_server.load("config/game.config.ron"),
+    ));
+}
+
+fn apply_game_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    handle: Res<GameConfigHandle>,
+    configs: Res<Assets<GameConfig>>,
+    mut config: ResMut<GameConfig>,
+) {
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } if *id == handle.0.id() => {
+                if let Some(loaded) = configs.get(*id) {
+                    info!("Applying game config");
+                    *config = loaded.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+// The systems that can ask a character to move, from lowest to highest priority
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Reflect)]
+pub enum InputSource {
+    Ai,
+    // Keyboard and gamepad controllers
+    Local,
+    Touch,
+    Replay,
+    Network,
+}
+
+impl InputSource {
+    // Exclusive sources win whenever they contribute, even when asking the character to stand still.
+    // The other sources only take over while they are actually pushing the character somewhere.
+    pub fn is_exclusive(&self) -> bool {
+        matches!(self, InputSource::Replay | InputSource::Network)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Reflect)]
+pub struct MovementContribution {
+    pub source: InputSource,
+    pub direction: Vec2,
+}
+
+// The movement intent of a character for the current frame
+// Every input source contributes to it separately, and gameplay consumes the resolved
+// direction once per frame before clearing the contributions.
+#[derive(Clone, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CharacterMovement {
+    contributions: Vec<MovementContribution>,
+}
+
+impl CharacterMovement {
+    // Replaces any earlier contribution from the same source this frame
+    pub fn contribute(&mut self, source: InputSource, direction: Vec2) {
+        match self
+            .contributions
+            .iter_mut()
+            .find(|contribution| contribution.source == source)
+        {
+            Some(contribution) => contribution.direction = direction,
+            None => self
+                .contributions
+                .push(MovementContribution { source, direction }),
+        }
+    }
+
+    pub fn contributions(&self) -> &[MovementContribution] {
+        &self.contributions
+    }
+
+    // The contribution that wins arbitration: the highest priority source that is either
+    // exclusive or asking to move
+    pub fn resolved(&self) -> Option<MovementContribution> {
+        self.contributions
+            .iter()
+            .filter(|contribution| {
+                contribution.source.is_exclusive() || contribution.direction != Vec2::ZERO
+            })
+            .max_by_key(|contribution| contribution.source)
+            .copied()
+    }
+
+    pub fn resolve(&self) -> Vec2 {
+        self.resolved()
+            .map(|contribution| contribution.direction)
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        self.contributions.clear();
+    }
+}
@@ -1,10 +1,30 @@
-use bevy::prelude::*;
+use std::time::Duration;
 
-#[derive(Clone, Copy, Debug)]
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use serde::Deserialize;
+
+mod config;
+pub use config::*;
+mod intent;
+pub use intent::*;
+mod loader;
+pub use loader::*;
+mod registry;
+pub use registry::*;
+mod tiles;
+pub use tiles::*;
+
+#[derive(Clone, Copy, Debug, Default)]
 #[derive(Component, Reflect)]
+#[reflect(Component)]
 pub struct Character;
 
 impl Character {
+    pub const RADIUS: f32 = 96.;
+
     pub fn bundle() -> CharacterBundle {
         CharacterBundle::default()
     }
@@ -16,6 +36,13 @@ pub struct CharacterBundle {
     character: Character,
     name: Name,
     movement: CharacterMovement,
+    velocity: Velocity,
+    movement_profile: MovementProfile,
+    movement_profile_source: MovementProfileSource,
+    facing: CharacterFacing,
+    health: Health,
+    score: Score,
+    collider: Collider,
     spatial: SpatialBundle,
 }
 
@@ -25,11 +52,361 @@ impl Default for CharacterBundle {
             character: Character,
             name: Name::new("Character"),
             movement: CharacterMovement::default(),
+            velocity: Velocity::default(),
+            movement_profile: MovementProfile::default(),
+            movement_profile_source: MovementProfileSource::default(),
+            facing: CharacterFacing::default(),
+            health: Health::default(),
+            score: Score::default(),
+            collider: Collider::circle(Character::RADIUS),
             spatial: SpatialBundle::default(),
         }
     }
 }
 
+impl CharacterBundle {
+    pub fn with_translation(mut self, translation: Vec3) -> Self {
+        self.spatial.transform.translation = translation;
+        self
+    }
+
+    // The character keeps this profile instead of the one in the `GameConfig`
+    pub fn with_movement_profile(mut self, movement_profile: MovementProfile) -> Self {
+        self.movement_profile = movement_profile;
+        self.movement_profile_source = MovementProfileSource::Own;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Velocity(pub Vec2);
+
+// Describes how quickly a character reaches the velocity its movement intent asks for
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct MovementProfile {
+    // Units per second squared when speeding up
+    pub acceleration: f32,
+    // Units per second squared when slowing down or stopping
+    pub deceleration: f32,
+    // Units per second at full intent
+    pub max_speed: f32,
+    // Radians per second the direction of travel can rotate
+    pub turn_rate: f32,
+}
+
+impl Default for MovementProfile {
+    fn default() -> Self {
+        MovementProfile {
+            acceleration: 900.,
+            deceleration: 1200.,
+            max_speed: 150.,
+            turn_rate: std::f32::consts::TAU,
+        }
+    }
+}
+
+// Where the `MovementProfile` of a character comes from
+// Characters following the config get its profile when they spawn and whenever it changes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub enum MovementProfileSource {
+    #[default]
+    Config,
+    // Given to the character, such as by its blueprint, and kept when the config changes
+    Own,
+}
+
+// The last direction the character moved in, used to aim projectiles
+#[derive(Clone, Copy, Debug)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CharacterFacing(pub Vec2);
+
+impl Default for CharacterFacing {
+    fn default() -> Self {
+        CharacterFacing(Vec2::X)
+    }
+}
+
+// Inserted by controllers when the character should fire, and consumed by gameplay
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CharacterFire;
+
+// The player index whose inputs drive this character in a rollback session
+// Every peer gives a player the same index, so gameplay uses it to make the same choices on each of them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Component, Reflect)]
+pub struct RollbackPlayer(pub usize);
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Health { current: max, max }
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Health::new(100.)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Collider {
+    pub radius: f32,
+}
+
+impl Collider {
+    pub fn circle(radius: f32) -> Self {
+        Collider { radius }
+    }
+
+    pub fn overlaps(&self, position: Vec2, other: &Collider, other_position: Vec2) -> bool {
+        position.distance(other_position) < self.radius + other.radius
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component, Reflect)]
+pub struct Projectile {
+    pub owner: Entity,
+    pub direction: Vec2,
+    pub speed: f32,
+    pub damage: f32,
+}
+
+// Restoring a rollback snapshot can give the owner a new id
+impl MapEntities for Projectile {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.owner = entity_mapper.get_or_reserve(self.owner);
+    }
+}
+
+impl Projectile {
+    pub const SPEED: f32 = 600.;
+    pub const DAMAGE: f32 = 10.;
+    pub const LIFETIME: Duration = Duration::from_millis(1500);
+    pub const RADIUS: f32 = 12.;
+
+    pub fn bundle(owner: Entity, origin: Vec3, direction: Vec2) -> ProjectileBundle {
+        ProjectileBundle {
+            projectile: Projectile {
+                owner,
+                direction: direction.normalize_or_zero(),
+                speed: Projectile::SPEED,
+                damage: Projectile::DAMAGE,
+            },
+            lifetime: Lifetime(Timer::new(Projectile::LIFETIME, TimerMode::Once)),
+            collider: Collider::circle(Projectile::RADIUS),
+            name: Name::new("Projectile"),
+            spatial: SpatialBundle::from_transform(Transform::from_translation(origin + Vec3::Z)),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(Bundle)]
+pub struct ProjectileBundle {
+    projectile: Projectile,
+    lifetime: Lifetime,
+    collider: Collider,
+    name: Name,
+    spatial: SpatialBundle,
+}
+
+// Entities with a lifetime are despawned once the timer finishes
+#[derive(Clone, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Lifetime(pub Timer);
+
+// Each character keeps track of its own score
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Score(pub u32);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Reflect)]
+pub enum PickupKind {
+    #[default]
+    Coin,
+    // Restores the given amount of health
+    Heal(f32),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Reflect)]
+pub enum PickupRespawn {
+    #[default]
+    Never,
+    After(Duration),
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    pub score: u32,
+    pub respawn: PickupRespawn,
+}
+
+impl Pickup {
+    pub const RADIUS: f32 = 24.;
+
+    pub fn coin(position: Vec2) -> PickupBundle {
+        PickupBundle::new(
+            Pickup {
+                kind: PickupKind::Coin,
+                score: 10,
+                respawn: PickupRespawn::Never,
+            },
+            position,
+        )
+    }
+
+    pub fn heal(position: Vec2) -> PickupBundle {
+        PickupBundle::new(
+            Pickup {
+                kind: PickupKind::Heal(25.),
+                score: 0,
+                respawn: PickupRespawn::Never,
+            },
+            position,
+        )
+    }
+}
+
+#[derive(Debug)]
+#[derive(Bundle)]
+pub struct PickupBundle {
+    pickup: Pickup,
+    collider: Collider,
+    name: Name,
+    spatial: SpatialBundle,
+}
+
+impl PickupBundle {
+    fn new(pickup: Pickup, position: Vec2) -> Self {
+        let name = match pickup.kind {
+            PickupKind::Coin => "Coin",
+            PickupKind::Heal(_) => "Heal",
+        };
+        PickupBundle {
+            pickup,
+            collider: Collider::circle(Pickup::RADIUS),
+            name: Name::new(name),
+            spatial: SpatialBundle::from_transform(Transform::from_translation(
+                position.extend(0.),
+            )),
+        }
+    }
+
+    pub fn respawning_after(mut self, delay: Duration) -> Self {
+        self.pickup.respawn = PickupRespawn::After(delay);
+        self
+    }
+}
+
+// Present on a collected pickup until it becomes available again
+#[derive(Clone, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PickupCooldown(pub Timer);
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Event)]
+pub struct PickupCollected {
+    pub character: Entity,
+    pub pickup: Entity,
+    pub kind: PickupKind,
+    pub score: u32,
+    pub position: Vec2,
+}
+
+// Characters are marked dead once they run out of health
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Dead;
+
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ExitZone;
+
+impl ExitZone {
+    pub const RADIUS: f32 = 64.;
+
+    pub fn bundle(position: Vec2) -> ExitZoneBundle {
+        ExitZoneBundle {
+            exit_zone: ExitZone,
+            collider: Collider::circle(ExitZone::RADIUS),
+            name: Name::new("Exit Zone"),
+            spatial: SpatialBundle::from_transform(Transform::from_translation(
+                position.extend(-1.),
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(Bundle)]
+pub struct ExitZoneBundle {
+    exit_zone: ExitZone,
+    collider: Collider,
+    name: Name,
+    spatial: SpatialBundle,
+}
+
+// Replays a recorded run. Ghosts look like characters but have no collider and take no part in gameplay.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Ghost;
+
+impl Ghost {
+    pub fn bundle(position: Vec2) -> GhostBundle {
+        GhostBundle {
+            ghost: Ghost,
+            name: Name::new("Ghost"),
+            spatial: SpatialBundle::from_transform(Transform::from_translation(
+                position.extend(-0.5),
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(Bundle)]
+pub struct GhostBundle {
+    ghost: Ghost,
+    name: Name,
+    spatial: SpatialBundle,
+}
+
+// The camera the game is seen through
+// Tools such as the editor may add cameras of their own, so systems that convert screen positions
+// to world positions for gameplay look for this one
 #[derive(Clone, Copy, Debug, Default)]
 #[derive(Component, Reflect)]
-pub struct CharacterMovement(pub Vec2);
+#[reflect(Component)]
+pub struct GameCamera;
@@ -0,0 +1,196 @@
+use std::any::TypeId;
+
+use bevy::{ecs::system::Command, prelude::*};
+
+use crate::{Character, ExitZone, Pickup, Terrain, TerrainKind, Wall};
+
+pub struct BlueprintsPlugin;
+
+/// This plugin registers the blueprints defined in this crate to the [`BlueprintRegistry`]
+impl Plugin for BlueprintsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_blueprint::<Character>("Characters", "Character", |entity| {
+            entity.insert(Character::bundle());
+        })
+        .register_blueprint::<Pickup>("Level", "Coin", |entity| {
+            entity.insert(Pickup::coin(Vec2::ZERO));
+        })
+        .register_blueprint::<Pickup>("Level", "Heal", |entity| {
+            entity.insert(Pickup::heal(Vec2::ZERO));
+        })
+        .register_blueprint::<ExitZone>("Level", "Exit Zone", |entity| {
+            entity.insert(ExitZone::bundle(Vec2::ZERO));
+        })
+        .register_blueprint::<Terrain>(Terrain::CATEGORY, "Grass", |entity| {
+            entity.insert(Terrain::bundle(TerrainKind::Grass, IVec2::ZERO));
+        })
+        .register_blueprint::<Terrain>(Terrain::CATEGORY, "Sand", |entity| {
+            entity.insert(Terrain::bundle(TerrainKind::Sand, IVec2::ZERO));
+        })
+        .register_blueprint::<Terrain>(Terrain::CATEGORY, "Water", |entity| {
+            entity.insert(Terrain::bundle(TerrainKind::Water, IVec2::ZERO));
+        })
+        .register_blueprint::<Terrain>(Terrain::CATEGORY, "Stone", |entity| {
+            entity.insert(Terrain::bundle(TerrainKind::Stone, IVec2::ZERO));
+        })
+        .register_blueprint::<Wall>(Wall::CATEGORY, "Wall", |entity| {
+            entity.insert(Wall::bundle(IVec2::ZERO));
+        });
+    }
+}
+
+// Something tools can add to the world, such as a bundle of components with default values
+#[derive(Clone, Debug)]
+pub struct Blueprint {
+    pub category: &'static str,
+    pub name: &'static str,
+    // Inserts the default components of the blueprint
+    pub add_to_entity: fn(&mut EntityWorldMut),
+    // The component that identifies entities made from this blueprint
+    marker: TypeId,
+    // Every component the blueprint inserts
+    components: Vec<TypeId>,
+}
+
+impl Blueprint {
+    pub fn marker(&self) -> TypeId {
+        self.marker
+    }
+
+    pub fn components(&self) -> &[TypeId] {
+        &self.components
+    }
+}
+
+// Filled by each crate's plugin and read by the editor, level scenes and debug tools
+#[derive(Debug, Default)]
+#[derive(Resource)]
+pub struct BlueprintRegistry {
+    blueprints: Vec<Blueprint>,
+}
+
+impl BlueprintRegistry {
+    pub fn iter(&self) -> impl Iterator<Item = &Blueprint> {
+        self.blueprints.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Blueprint> {
+        self.blueprints
+            .iter()
+            .find(|blueprint| blueprint.name == name)
+    }
+
+    // Whether an entity was made from any blueprint
+    pub fn matches(&self, entity: &EntityRef) -> bool {
+        self.find(entity).is_some()
+    }
+
+    // The blueprint an entity was made from
+    // Blueprints that share a marker, such as "Coin" and "Heal", are told apart by the entity's `Name`
+    pub fn find(&self, entity: &EntityRef) -> Option<&Blueprint> {
+        let name = entity.get::<Name>();
+        let mut matching = self
+            .blueprints
+            .iter()
+            .filter(|blueprint| entity.contains_type_id(blueprint.marker));
+        matching
+            .clone()
+            .find(|blueprint| name.is_some_and(|name| name.as_str() == blueprint.name))
+            .or_else(|| matching.next())
+    }
+
+    fn register(
+        &mut self,
+        category: &'static str,
+        name: &'static str,
+        marker: TypeId,
+        add_to_entity: fn(&mut EntityWorldMut),
+    ) {
+        // The components are found by adding the blueprint to an entity of an empty world
+        let mut world = World::new();
+        let mut entity = world.spawn_empty();
+        add_to_entity(&mut entity);
+        let entity = entity.id();
+        let components = world
+            .inspect_entity(entity)
+            .iter()
+            .filter_map(|info| info.type_id())
+            .collect();
+        self.blueprints.push(Blueprint {
+            category,
+            name,
+            add_to_entity,
+            marker,
+            components,
+        });
+    }
+}
+
+pub trait BlueprintApp {
+    // `M` is the component that every entity made from this blueprint has
+    fn register_blueprint<M: Component>(
+        &mut self,
+        category: &'static str,
+        name: &'static str,
+        add_to_entity: fn(&mut EntityWorldMut),
+    ) -> &mut Self;
+}
+
+impl BlueprintApp for App {
+    fn register_blueprint<M: Component>(
+        &mut self,
+        category: &'static str,
+        name: &'static str,
+        add_to_entity: fn(&mut EntityWorldMut),
+    ) -> &mut Self {
+        self.init_resource::<BlueprintRegistry>();
+        self.world.resource_mut::<BlueprintRegistry>().register(
+            category,
+            name,
+            TypeId::of::<M>(),
+            add_to_entity,
+        );
+        self
+    }
+}
+
+// Spawns a registered blueprint by name, for debugging and tools
+#[derive(Clone, Debug)]
+pub struct SpawnBlueprint {
+    pub name: String,
+    pub position: Vec2,
+}
+
+impl SpawnBlueprint {
+    pub fn new(name: impl Into<String>) -> Self {
+        SpawnBlueprint {
+            name: name.into(),
+            position: Vec2::ZERO,
+        }
+    }
+
+    pub fn at(mut self, position: Vec2) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+impl Command for SpawnBlueprint {
+    fn apply(self, world: &mut World) {
+        let Some(add_to_entity) = world
+            .get_resource::<BlueprintRegistry>()
+            .and_then(|registry| registry.get(&self.name))
+            .map(|blueprint| blueprint.add_to_entity)
+        else {
+            warn!("No blueprint named \"{}\" is registered", self.name);
+            return;
+        };
+        let mut entity = world.spawn_empty();
+        add_to_entity(&mut entity);
+        if let Some(mut transform) = entity.get_mut::<Transform>() {
+            // Blueprints keep their own depth
+            transform.translation = self.position.extend(transform.translation.z);
+        }
+        debug!("Spawned blueprint \"{}\" as {:?}", self.name, entity.id());
+    }
+}
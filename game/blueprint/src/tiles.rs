@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+
+// Levels are painted on a grid of square tiles, centered on multiples of the tile size
+pub const TILE_SIZE: f32 = 64.;
+
+// The cell of the tile grid that contains a position
+pub fn tile_cell(position: Vec2) -> IVec2 {
+    (position / TILE_SIZE).round().as_ivec2()
+}
+
+// The center of a cell of the tile grid
+pub fn tile_position(cell: IVec2) -> Vec2 {
+    cell.as_vec2() * TILE_SIZE
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Reflect)]
+pub enum TerrainKind {
+    #[default]
+    Grass,
+    Sand,
+    Water,
+    Stone,
+}
+
+impl TerrainKind {
+    pub const ALL: [TerrainKind; 4] = [
+        TerrainKind::Grass,
+        TerrainKind::Sand,
+        TerrainKind::Water,
+        TerrainKind::Stone,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TerrainKind::Grass => "Grass",
+            TerrainKind::Sand => "Sand",
+            TerrainKind::Water => "Water",
+            TerrainKind::Stone => "Stone",
+        }
+    }
+}
+
+// The ground of a level. Terrain tiles are only drawn and take no part in gameplay.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Terrain(pub TerrainKind);
+
+impl Terrain {
+    pub const CATEGORY: &'static str = "Terrain";
+
+    pub fn bundle(kind: TerrainKind, cell: IVec2) -> TileBundle<Terrain> {
+        TileBundle::new(Terrain(kind), kind.name(), cell, -3.)
+    }
+}
+
+// A tile that characters cannot move through and that stops projectiles
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Wall;
+
+impl Wall {
+    pub const CATEGORY: &'static str = "Collision";
+
+    pub fn bundle(cell: IVec2) -> TileBundle<Wall> {
+        TileBundle::new(Wall, "Wall", cell, -2.)
+    }
+
+    // Pushes a circle out of the wall at `wall_position`, returning its corrected position
+    pub fn push_out(wall_position: Vec2, position: Vec2, radius: f32) -> Option<Vec2> {
+        let half_size = Vec2::splat(TILE_SIZE / 2.);
+        let closest = position.clamp(wall_position - half_size, wall_position + half_size);
+        let offset = position - closest;
+        if offset == Vec2::ZERO {
+            // The center is inside the wall, so leave through the nearest side
+            let inside = position - wall_position;
+            let depth = half_size - inside.abs();
+            return Some(if depth.x < depth.y {
+                Vec2::new(
+                    wall_position.x + inside.x.signum() * (half_size.x + radius),
+                    position.y,
+                )
+            } else {
+                Vec2::new(
+                    position.x,
+                    wall_position.y + inside.y.signum() * (half_size.y + radius),
+                )
+            });
+        }
+        (offset.length() < radius).then(|| closest + offset.normalize() * radius)
+    }
+}
+
+#[derive(Debug)]
+#[derive(Bundle)]
+pub struct TileBundle<T: Component> {
+    tile: T,
+    name: Name,
+    spatial: SpatialBundle,
+}
+
+impl<T: Component> TileBundle<T> {
+    fn new(tile: T, name: &'static str, cell: IVec2, depth: f32) -> Self {
+        TileBundle {
+            tile,
+            name: Name::new(name),
+            spatial: SpatialBundle::from_transform(Transform::from_translation(
+                tile_position(cell).extend(depth),
+            )),
+        }
+    }
+}
@@ -1,36 +1,61 @@
 use bevy::prelude::*;
 
-use leafwing_input_manager::prelude::{ActionState, Actionlike, InputManagerBundle, InputMap};
-
-use bevy_game_blueprints::CharacterMovement;
+use leafwing_input_manager::prelude::{
+    ActionState, Actionlike, DualAxis, InputManagerBundle, InputMap, VirtualDPad,
+};
 
 #[derive(Clone, Copy, Debug, Default)]
 #[derive(Component, Reflect)]
+#[reflect(Component)]
 pub enum Controller {
     #[default]
     One,
     Two,
 }
 
+impl Controller {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Controller::One => "Player 1",
+            Controller::Two => "Player 2",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[derive(Component, Reflect)]
+#[reflect(Component)]
 #[derive(Actionlike)]
 pub enum CharacterControl {
-    Up,
-    Down,
-    Left,
-    Right,
+    // A dual-axis action, read with `ActionState::axis_pair`
+    Move,
+    Fire,
 }
 
-impl From<&CharacterControl> for CharacterMovement {
-    fn from(control: &CharacterControl) -> Self {
-        match control {
-            CharacterControl::Up => CharacterMovement(Vec2::new(0.0, 1.0)),
-            CharacterControl::Down => CharacterMovement(Vec2::new(0.0, -1.0)),
-            CharacterControl::Left => CharacterMovement(Vec2::new(-1.0, 0.0)),
-            CharacterControl::Right => CharacterMovement(Vec2::new(1.0, 0.0)),
-        }
-    }
+// Presses injected by tools such as the editor, applied on top of the controller's own input
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct VirtualInput {
+    // Held as the `Move` axis until cleared
+    pub movement: Option<Vec2>,
+    // Pressed for a single frame
+    pub fire: bool,
+}
+
+// Each action with the inputs bound to it, for debugging tools
+pub fn describe_bindings(
+    input_map: &InputMap<CharacterControl>,
+) -> Vec<(CharacterControl, Vec<String>)> {
+    input_map
+        .iter()
+        .map(|(action, inputs)| {
+            (
+                *action,
+                inputs.iter().map(|input| format!("{input:?}")).collect(),
+            )
+        })
+        .collect()
 }
 
 #[derive(Bundle)]
@@ -39,8 +64,15 @@ pub struct ControllerBundle {
 }
 
 impl ControllerBundle {
-    fn new(bindings: [(KeyCode, CharacterControl); 4]) -> Self {
-        let input_map = InputMap::new(bindings).build();
+    fn new(keys: VirtualDPad, fire: KeyCode, gamepad: Gamepad) -> Self {
+        let input_map = InputMap::default()
+            .insert(keys, CharacterControl::Move)
+            .insert(DualAxis::left_stick(), CharacterControl::Move)
+            .insert(VirtualDPad::dpad(), CharacterControl::Move)
+            .insert(fire, CharacterControl::Fire)
+            .insert(GamepadButtonType::South, CharacterControl::Fire)
+            .set_gamepad(gamepad)
+            .build();
         ControllerBundle {
             input_manager: InputManagerBundle::<CharacterControl> {
                 action_state: ActionState::default(),
@@ -50,20 +82,14 @@ impl ControllerBundle {
     }
 
     pub fn key_controller_one() -> Self {
-        Self::new([
-            (KeyCode::W, CharacterControl::Up),
-            (KeyCode::A, CharacterControl::Left),
-            (KeyCode::S, CharacterControl::Down),
-            (KeyCode::D, CharacterControl::Right),
-        ])
+        Self::new(VirtualDPad::wasd(), KeyCode::Space, Gamepad::new(0))
     }
 
     pub fn key_controller_two() -> Self {
-        Self::new([
-            (KeyCode::Up, CharacterControl::Up),
-            (KeyCode::Left, CharacterControl::Left),
-            (KeyCode::Down, CharacterControl::Down),
-            (KeyCode::Right, CharacterControl::Right),
-        ])
+        Self::new(
+            VirtualDPad::arrow_keys(),
+            KeyCode::RControl,
+            Gamepad::new(1),
+        )
     }
 }
@@ -1,6 +1,10 @@
 use bevy::{input::InputSystem as BevyInputSystem, prelude::*};
 
-use leafwing_input_manager::plugin::InputManagerPlugin;
+use bevy_game_blueprints::BlueprintApp;
+
+use leafwing_input_manager::plugin::{InputManagerPlugin, InputManagerSystem};
+// Lets other crates read controllers without depending on leafwing-input-manager
+pub use leafwing_input_manager::prelude::{ActionState, InputMap};
 
 mod controllers;
 pub use controllers::*;
@@ -9,6 +13,16 @@ mod systems;
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
 pub struct ControllerSet;
 
+// The parts of `ControllerSet`, so they can be turned off one at a time
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
+pub enum ControllerSystem {
+    // Gives entities the input map of their controller when it changes
+    Sync,
+    // Keyboard, gamepad and virtual input
+    Input,
+    Touch,
+}
+
 /// This plugin adds controllers using leafwing-input-manager that are used to attach
 /// components for further processing in game ticks.
 pub struct ControllerPlugin;
@@ -20,16 +34,30 @@ impl Plugin for ControllerPlugin {
             .add_systems(
                 PreUpdate,
                 (
-                    systems::sync_controllers,
-                    systems::queue_inputs,
-                    systems::set_mobile_actions,
+                    systems::sync_controllers.in_set(ControllerSystem::Sync),
+                    (
+                        systems::apply_virtual_input.after(InputManagerSystem::Update),
+                        systems::queue_inputs,
+                    )
+                        .chain()
+                        .in_set(ControllerSystem::Input),
+                    (systems::set_mobile_actions, systems::set_mobile_fire)
+                        .chain()
+                        .in_set(ControllerSystem::Touch),
                 )
                     .chain()
                     .in_set(ControllerSet),
-            );
+            )
+            .register_blueprint::<Controller>("Controls", "Player One Controller", |entity| {
+                entity.insert(Controller::One);
+            })
+            .register_blueprint::<Controller>("Controls", "Player Two Controller", |entity| {
+                entity.insert(Controller::Two);
+            });
 
         #[cfg(debug_assertions)]
         app.register_type::<Controller>()
-            .register_type::<CharacterControl>();
+            .register_type::<CharacterControl>()
+            .register_type::<VirtualInput>();
     }
 }
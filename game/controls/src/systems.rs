@@ -1,10 +1,10 @@
 use bevy::prelude::*;
 
-use leafwing_input_manager::prelude::ActionState;
+use leafwing_input_manager::{axislike::DualAxisData, prelude::ActionState};
 
 use bevy_game_blueprints::*;
 
-use crate::{CharacterControl, Controller, ControllerBundle};
+use crate::{CharacterControl, Controller, ControllerBundle, VirtualInput};
 
 pub(crate) fn sync_controllers(
     mut commands: Commands,
@@ -31,41 +31,178 @@ pub(crate) fn sync_controllers(
     }
 }
 
+// Runs after leafwing-input-manager updates the action state from the real input
+pub(crate) fn apply_virtual_input(
+    mut input_query: Query<(&mut ActionState<CharacterControl>, &mut VirtualInput)>,
+) {
+    for (mut action_state, mut virtual_input) in input_query.iter_mut() {
+        if let Some(movement) = virtual_input.movement {
+            action_state.press(CharacterControl::Move);
+            action_state
+                .action_data_mut(CharacterControl::Move)
+                .axis_pair = Some(DualAxisData::from_xy(movement));
+        }
+        if virtual_input.fire {
+            action_state.press(CharacterControl::Fire);
+            virtual_input.fire = false;
+        }
+    }
+}
+
+// The config can set any value, and a dead zone of 1 or more would divide by zero below
+const MAX_DEAD_ZONE: f32 = 0.95;
+// A response exponent of 0 or less would push small movements to full speed or infinity
+const MIN_RESPONSE_EXPONENT: f32 = 0.1;
+
+// Applies the dead zone and response curve to a raw movement axis
+// Keyboard diagonals report a length above 1, so the magnitude is clamped first
+fn shape_movement(axis: Vec2, config: &ControlsConfig) -> Vec2 {
+    // Written without `clamp`, which would keep a NaN
+    let dead_zone = if config.dead_zone < MAX_DEAD_ZONE {
+        config.dead_zone.max(0.)
+    } else {
+        MAX_DEAD_ZONE
+    };
+    let response_exponent = config.response_exponent.max(MIN_RESPONSE_EXPONENT);
+    let magnitude = axis.length().min(1.);
+    if magnitude <= dead_zone {
+        return Vec2::ZERO;
+    }
+    let scaled = (magnitude - dead_zone) / (1. - dead_zone);
+    axis.normalize() * scaled.powf(response_exponent)
+}
+
 pub(crate) fn queue_inputs(
     mut commands: Commands,
-    actions_query: Query<(Entity, &ActionState<CharacterControl>)>,
+    config: Res<GameConfig>,
+    mut actions_query: Query<(
+        Entity,
+        &ActionState<CharacterControl>,
+        Option<&mut CharacterMovement>,
+    )>,
 ) {
-    for (entity, action_state) in actions_query.iter() {
-        let mut movement = Vec2::ZERO;
-        for control in action_state.get_pressed() {
-            movement += CharacterMovement::from(&control).0;
+    for (entity, action_state, movement) in actions_query.iter_mut() {
+        if let Some(mut movement) = movement {
+            let direction = action_state
+                .axis_pair(CharacterControl::Move)
+                .map(|axis| shape_movement(axis.xy(), &config.controls))
+                .unwrap_or_default();
+            movement.contribute(InputSource::Local, direction);
         }
-        if movement != Vec2::ZERO {
-            commands.entity(entity).insert(CharacterMovement(movement));
+        if action_state.just_pressed(CharacterControl::Fire) {
+            commands.entity(entity).insert(CharacterFire);
         }
     }
 }
 
-pub const FOLLOW_EPSILON: f32 = 5.;
+// Touch drives player one's character, like the first keyboard layout
+fn is_touch_controlled(controller: &Controller) -> bool {
+    matches!(controller, Controller::One)
+}
 
+// The first touch acts as a virtual joystick centered on the touch-controlled character
 // TODO: does leafwing have an API for this?
 pub(crate) fn set_mobile_actions(
+    config: Res<GameConfig>,
     touch_input: Res<Touches>,
-    mut actions: Query<(&Transform, &mut CharacterMovement), With<Character>>,
-    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut actions: Query<(&Transform, &mut CharacterMovement, &Controller), With<Character>>,
+    camera: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
 ) {
-    for (transform, mut movement) in actions.iter_mut() {
+    // Touches are relative to the game camera, even while a tool such as the editor shows another one
+    let Ok((camera, camera_transform)) = camera.get_single() else {
+        return;
+    };
+    for (transform, mut movement, controller) in actions.iter_mut() {
+        if !is_touch_controlled(controller) {
+            continue;
+        }
         if let Some(touch_position) = touch_input.first_pressed_position() {
-            // TODO: does this need to be generalized for use with the editor?
-            let (camera, camera_transform) = camera.single();
             if let Some(touch_position) =
                 camera.viewport_to_world_2d(camera_transform, touch_position)
             {
                 let diff = touch_position - transform.translation.xy();
-                if diff.length() > FOLLOW_EPSILON {
-                    movement.0 = diff;
+                if diff.length() > config.controls.follow_epsilon {
+                    let axis = diff / config.controls.touch_radius;
+                    movement.contribute(InputSource::Touch, shape_movement(axis, &config.controls));
                 }
             }
         }
     }
 }
+
+// The first touch steers the character, so any additional tap fires
+pub(crate) fn set_mobile_fire(
+    mut commands: Commands,
+    touch_input: Res<Touches>,
+    characters: Query<(Entity, &Controller), With<Character>>,
+) {
+    if touch_input.any_just_pressed() && touch_input.iter().count() > 1 {
+        for (entity, controller) in characters.iter() {
+            if is_touch_controlled(controller) {
+                commands.entity(entity).insert(CharacterFire);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dead_zone: f32, response_exponent: f32) -> ControlsConfig {
+        ControlsConfig {
+            dead_zone,
+            response_exponent,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn movement_inside_the_dead_zone_is_dropped() {
+        let config = config(0.2, 1.);
+        assert_eq!(shape_movement(Vec2::ZERO, &config), Vec2::ZERO);
+        assert_eq!(shape_movement(Vec2::new(0.2, 0.), &config), Vec2::ZERO);
+        assert!(shape_movement(Vec2::new(0.21, 0.), &config).x > 0.);
+        assert_eq!(shape_movement(Vec2::new(0., -1.), &config), Vec2::NEG_Y);
+    }
+
+    #[test]
+    fn keyboard_diagonals_are_clamped_to_full_speed() {
+        let config = config(0.15, 1.5);
+        let movement = shape_movement(Vec2::ONE, &config);
+        assert!((movement.length() - 1.).abs() < 1e-6);
+        assert!((movement.x - movement.y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn response_curve_slows_small_movements() {
+        let config = config(0., 2.);
+        let movement = shape_movement(Vec2::new(0.5, 0.), &config);
+        assert!((movement.x - 0.25).abs() < 1e-6);
+        assert_eq!(movement.y, 0.);
+    }
+
+    #[test]
+    fn invalid_configs_give_finite_movement() {
+        for config in [
+            config(1., 1.5),
+            config(2., 1.5),
+            config(-1., 1.5),
+            config(f32::NAN, 1.5),
+            config(0.15, 0.),
+            config(0.15, -1.),
+            config(0.15, f32::NAN),
+        ] {
+            for axis in [Vec2::ZERO, Vec2::new(0.5, 0.), Vec2::X, Vec2::ONE] {
+                let movement = shape_movement(axis, &config);
+                assert!(
+                    movement.is_finite(),
+                    "{axis} gave {movement} with {config:?}"
+                );
+                assert!(movement.length() <= 1. + 1e-6);
+            }
+        }
+        // The dead zone never swallows a full movement
+        assert!(shape_movement(Vec2::X, &config(1., 1.5)).x > 0.);
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
-use bevy_game_blueprints::CharacterMovement;
-use bevy_kira_audio::{Audio, AudioControl, AudioInstance, AudioPlugin, AudioTween, PlaybackState};
+use bevy_game_blueprints::{CharacterMovement, GameConfig, PickupCollected, Projectile};
+use bevy_kira_audio::{
+    Audio, AudioApp, AudioChannel, AudioControl, AudioInstance, AudioPlugin, AudioTween,
+    PlaybackState,
+};
 
 use crate::loading::{AssetLoadState, AudioAssets};
 
@@ -8,18 +11,45 @@ use crate::loading::{AssetLoadState, AudioAssets};
 #[derive(SystemSet)]
 pub struct AudioSet;
 
+// The parts of `AudioSet`, so they can be turned off one at a time
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(SystemSet)]
+pub enum AudioSystem {
+    Config,
+    // The looping sound of moving characters
+    Movement,
+    // One-shot sounds for firing and pickups
+    Effects,
+}
+
 pub struct GameAudioPlugin;
 
 // This plugin is responsible to control the game audio
 impl Plugin for GameAudioPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(AudioPlugin)
+            .add_audio_channel::<EffectsChannel>()
             .add_systems(OnEnter(AssetLoadState::Active), start_audio)
             .add_systems(
                 Update,
-                control_flying_sound.in_set(AudioSet).run_if(
-                    resource_exists::<AudioAssets>().and_then(resource_exists::<MovementAudio>()),
-                ),
+                (
+                    apply_audio_config
+                        .run_if(
+                            resource_exists::<MovementAudio>()
+                                .and_then(resource_exists_and_changed::<GameConfig>()),
+                        )
+                        .in_set(AudioSystem::Config),
+                    control_flying_sound
+                        .run_if(
+                            resource_exists::<AudioAssets>()
+                                .and_then(resource_exists::<MovementAudio>()),
+                        )
+                        .in_set(AudioSystem::Movement),
+                    (play_fire_sound, play_pickup_sound)
+                        .run_if(resource_exists::<AudioAssets>())
+                        .in_set(AudioSystem::Effects),
+                )
+                    .in_set(AudioSet),
             );
 
         #[cfg(debug_assertions)]
@@ -27,19 +57,38 @@ impl Plugin for GameAudioPlugin {
     }
 }
 
+// One-shot sounds play on their own channel so they are unaffected by pausing the main one
+#[derive(Resource)]
+struct EffectsChannel;
+
 #[derive(Resource, Reflect)]
 struct MovementAudio(Handle<AudioInstance>);
 
-fn start_audio(mut commands: Commands, audio_assets: Res<AudioAssets>, audio: Res<Audio>) {
+fn start_audio(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
     audio.pause();
     let handle = audio
         .play(audio_assets.flying.clone())
         .looped()
-        .with_volume(0.3)
+        .with_volume(config.audio.movement_volume)
         .handle();
     commands.insert_resource(MovementAudio(handle));
 }
 
+fn apply_audio_config(
+    config: Res<GameConfig>,
+    audio: Res<MovementAudio>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    if let Some(instance) = audio_instances.get_mut(&audio.0) {
+        instance.set_volume(config.audio.movement_volume, AudioTween::default());
+    }
+}
+
 fn control_flying_sound(
     query: Query<&CharacterMovement>,
     audio: Res<MovementAudio>,
@@ -48,12 +97,18 @@ fn control_flying_sound(
     if let Some(instance) = audio_instances.get_mut(&audio.0) {
         match instance.state() {
             PlaybackState::Paused { .. } => {
-                if query.iter().any(|movement| movement.0 != Vec2::ZERO) {
+                if query
+                    .iter()
+                    .any(|movement| movement.resolve() != Vec2::ZERO)
+                {
                     instance.resume(AudioTween::default());
                 }
             }
             PlaybackState::Playing { .. } => {
-                if query.iter().all(|movement| movement.0 == Vec2::ZERO) {
+                if query
+                    .iter()
+                    .all(|movement| movement.resolve() == Vec2::ZERO)
+                {
                     instance.pause(AudioTween::default());
                 }
             }
@@ -61,3 +116,23 @@ fn control_flying_sound(
         }
     }
 }
+
+fn play_fire_sound(
+    query: Query<(), Added<Projectile>>,
+    audio_assets: Res<AudioAssets>,
+    effects: Res<AudioChannel<EffectsChannel>>,
+) {
+    for _ in query.iter() {
+        effects.play(audio_assets.fire.clone()).with_volume(0.5);
+    }
+}
+
+fn play_pickup_sound(
+    mut pickup_events: EventReader<PickupCollected>,
+    audio_assets: Res<AudioAssets>,
+    effects: Res<AudioChannel<EffectsChannel>>,
+) {
+    for _ in pickup_events.read() {
+        effects.play(audio_assets.pickup.clone()).with_volume(0.5);
+    }
+}
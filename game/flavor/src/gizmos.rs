@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+use bevy_game_blueprints::{
+    Character, CharacterMovement, Collider, GameCamera, GameConfig, InputSource, Wall, TILE_SIZE,
+};
+
+use crate::graphics::{GraphicsSet, GraphicsSystem};
+
+const TOGGLE_KEY: KeyCode = KeyCode::F4;
+
+pub struct DebugGizmosPlugin;
+
+/// This plugin draws debug gizmos for movement, touch input and colliders
+/// F4 toggles every gizmo, and each kind can be toggled on the [`DebugGizmos`] resource,
+/// for example from the editor's resource inspector
+/// There is no AI or pathfinding yet, so `InputSource::Ai` contributions are the only AI intent drawn
+impl Plugin for DebugGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugGizmos>()
+            .add_systems(Update, toggle_debug_gizmos)
+            .add_systems(
+                Update,
+                (
+                    draw_movement_gizmos.run_if(gizmos_enabled(|gizmos| gizmos.movement)),
+                    draw_touch_gizmos.run_if(gizmos_enabled(|gizmos| gizmos.touch)),
+                    draw_collider_gizmos.run_if(gizmos_enabled(|gizmos| gizmos.colliders)),
+                )
+                    .after(toggle_debug_gizmos)
+                    // Gameplay clears the movement intent once it is applied
+                    .in_set(GraphicsSet)
+                    .in_set(GraphicsSystem::Gizmos),
+            );
+
+        #[cfg(debug_assertions)]
+        app.register_type::<DebugGizmos>();
+    }
+}
+
+#[derive(Clone, Debug)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct DebugGizmos {
+    pub enabled: bool,
+    // Every movement contribution of a character, colored by input source
+    pub movement: bool,
+    // The touch-follow target with the follow epsilon and touch radius around each character
+    pub touch: bool,
+    pub colliders: bool,
+}
+
+impl Default for DebugGizmos {
+    fn default() -> Self {
+        DebugGizmos {
+            enabled: false,
+            movement: true,
+            touch: true,
+            colliders: true,
+        }
+    }
+}
+
+fn gizmos_enabled(kind: fn(&DebugGizmos) -> bool) -> impl Fn(Res<DebugGizmos>) -> bool {
+    move |gizmos: Res<DebugGizmos>| gizmos.enabled && kind(&gizmos)
+}
+
+fn toggle_debug_gizmos(keys: Res<Input<KeyCode>>, mut gizmos: ResMut<DebugGizmos>) {
+    if keys.just_pressed(TOGGLE_KEY) {
+        gizmos.enabled = !gizmos.enabled;
+    }
+}
+
+fn source_color(source: InputSource) -> Color {
+    match source {
+        InputSource::Ai => Color::FUCHSIA,
+        InputSource::Local => Color::CYAN,
+        InputSource::Touch => Color::ORANGE,
+        InputSource::Replay => Color::YELLOW,
+        InputSource::Network => Color::LIME_GREEN,
+    }
+}
+
+fn draw_movement_gizmos(
+    mut gizmos: Gizmos,
+    character_query: Query<(&GlobalTransform, &CharacterMovement)>,
+) {
+    for (transform, movement) in character_query.iter() {
+        let position = transform.translation().truncate();
+        for contribution in movement.contributions() {
+            gizmos.ray_2d(
+                position,
+                contribution.direction * Character::RADIUS,
+                source_color(contribution.source),
+            );
+        }
+        // The resolved intent is drawn past the contributions so it stays visible
+        gizmos.ray_2d(
+            position,
+            movement.resolve() * Character::RADIUS * 1.25,
+            Color::WHITE,
+        );
+    }
+}
+
+fn draw_touch_gizmos(
+    mut gizmos: Gizmos,
+    config: Res<GameConfig>,
+    touch_input: Res<Touches>,
+    character_query: Query<&GlobalTransform, (With<Character>, With<CharacterMovement>)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+) {
+    let touch_position = touch_input.first_pressed_position().and_then(|position| {
+        let (camera, camera_transform) = camera_query.get_single().ok()?;
+        camera.viewport_to_world_2d(camera_transform, position)
+    });
+    for transform in character_query.iter() {
+        let position = transform.translation().truncate();
+        gizmos.circle_2d(position, config.controls.follow_epsilon, Color::ORANGE);
+        gizmos.circle_2d(position, config.controls.touch_radius, Color::ORANGE_RED);
+        if let Some(touch_position) = touch_position {
+            gizmos.line_2d(position, touch_position, Color::ORANGE);
+            gizmos.circle_2d(touch_position, 8., Color::ORANGE);
+        }
+    }
+}
+
+fn draw_collider_gizmos(
+    mut gizmos: Gizmos,
+    collider_query: Query<(&GlobalTransform, &Collider, &InheritedVisibility)>,
+    wall_query: Query<&GlobalTransform, With<Wall>>,
+) {
+    for (transform, collider, visibility) in collider_query.iter() {
+        // Collected pickups are hidden and can not be collided with until they respawn
+        let color = if visibility.get() {
+            Color::GREEN
+        } else {
+            Color::DARK_GRAY
+        };
+        gizmos.circle_2d(transform.translation().truncate(), collider.radius, color);
+    }
+    for transform in wall_query.iter() {
+        gizmos.rect_2d(
+            transform.translation().truncate(),
+            0.,
+            Vec2::splat(TILE_SIZE),
+            Color::GREEN,
+        );
+    }
+}
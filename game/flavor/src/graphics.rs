@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use bevy_game_blueprints::Character;
+use bevy_game_blueprints::{
+    Character, Dead, ExitZone, Ghost, Pickup, PickupCollected, PickupCooldown, PickupKind,
+    Projectile, Terrain, TerrainKind, Wall, TILE_SIZE,
+};
 
 use crate::loading::SpriteAssets;
 
@@ -8,15 +11,43 @@ use crate::loading::SpriteAssets;
 #[derive(SystemSet)]
 pub struct GraphicsSet;
 
+// The parts of `GraphicsSet`, so they can be turned off one at a time
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(SystemSet)]
+pub enum GraphicsSystem {
+    // Characters, ghosts and projectiles
+    Characters,
+    // Pickups, exits, terrain and walls
+    Level,
+    Effects,
+    Gizmos,
+}
+
 pub struct GameGraphicsPlugin;
 
 impl Plugin for GameGraphicsPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            render_player
-                .in_set(GraphicsSet)
-                .run_if(resource_exists::<SpriteAssets>()),
+            (
+                (
+                    (render_player, render_ghost, render_projectile)
+                        .in_set(GraphicsSystem::Characters),
+                    render_pickup.in_set(GraphicsSystem::Level),
+                    spawn_pickup_effects.in_set(GraphicsSystem::Effects),
+                )
+                    .run_if(resource_exists::<SpriteAssets>()),
+                (
+                    render_exit_zone,
+                    render_terrain,
+                    render_wall,
+                    hide_collected_pickups,
+                )
+                    .in_set(GraphicsSystem::Level),
+                render_dead_character.in_set(GraphicsSystem::Characters),
+                animate_pickup_effects.in_set(GraphicsSystem::Effects),
+            )
+                .in_set(GraphicsSet),
         );
     }
 }
@@ -34,3 +65,186 @@ fn render_player(
         });
     }
 }
+
+fn render_ghost(
+    mut commands: Commands,
+    ghost_query: Query<(Entity, &Transform), Added<Ghost>>,
+    textures: Res<SpriteAssets>,
+) {
+    for (ghost, transform) in ghost_query.iter() {
+        commands.entity(ghost).insert(SpriteBundle {
+            texture: textures.character.clone(),
+            transform: *transform,
+            sprite: Sprite {
+                color: Color::rgba(1., 1., 1., 0.35),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+}
+
+fn render_projectile(
+    mut commands: Commands,
+    projectile_query: Query<(Entity, &Transform), Added<Projectile>>,
+    textures: Res<SpriteAssets>,
+) {
+    for (projectile, transform) in projectile_query.iter() {
+        commands.entity(projectile).insert(SpriteBundle {
+            texture: textures.projectile.clone(),
+            transform: *transform,
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(2. * Projectile::RADIUS)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+}
+
+fn render_dead_character(mut character_query: Query<&mut Sprite, (With<Character>, Added<Dead>)>) {
+    for mut sprite in character_query.iter_mut() {
+        sprite.color = Color::rgba(0.3, 0.3, 0.3, 0.6);
+    }
+}
+
+fn render_exit_zone(
+    mut commands: Commands,
+    exit_query: Query<(Entity, &Transform), Added<ExitZone>>,
+) {
+    for (entity, transform) in exit_query.iter() {
+        commands.entity(entity).insert(SpriteBundle {
+            transform: *transform,
+            sprite: Sprite {
+                color: Color::rgba(0.2, 0.6, 1.0, 0.5),
+                custom_size: Some(Vec2::splat(2. * ExitZone::RADIUS)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+}
+
+fn terrain_color(kind: TerrainKind) -> Color {
+    match kind {
+        TerrainKind::Grass => Color::rgb(0.3, 0.55, 0.25),
+        TerrainKind::Sand => Color::rgb(0.85, 0.75, 0.5),
+        TerrainKind::Water => Color::rgb(0.2, 0.4, 0.8),
+        TerrainKind::Stone => Color::rgb(0.5, 0.5, 0.55),
+    }
+}
+
+fn render_terrain(
+    mut commands: Commands,
+    terrain_query: Query<(Entity, &Transform, &Terrain), Added<Terrain>>,
+) {
+    for (entity, transform, terrain) in terrain_query.iter() {
+        commands.entity(entity).insert(SpriteBundle {
+            transform: *transform,
+            sprite: Sprite {
+                color: terrain_color(terrain.0),
+                custom_size: Some(Vec2::splat(TILE_SIZE)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+}
+
+fn render_wall(mut commands: Commands, wall_query: Query<(Entity, &Transform), Added<Wall>>) {
+    for (entity, transform) in wall_query.iter() {
+        commands.entity(entity).insert(SpriteBundle {
+            transform: *transform,
+            sprite: Sprite {
+                color: Color::rgb(0.2, 0.2, 0.25),
+                custom_size: Some(Vec2::splat(TILE_SIZE)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+}
+
+fn pickup_color(kind: PickupKind) -> Color {
+    match kind {
+        PickupKind::Coin => Color::GOLD,
+        PickupKind::Heal(_) => Color::LIME_GREEN,
+    }
+}
+
+fn render_pickup(
+    mut commands: Commands,
+    pickup_query: Query<(Entity, &Transform, &Pickup), Added<Pickup>>,
+    textures: Res<SpriteAssets>,
+) {
+    for (entity, transform, pickup) in pickup_query.iter() {
+        commands.entity(entity).insert(SpriteBundle {
+            texture: textures.pickup.clone(),
+            transform: *transform,
+            sprite: Sprite {
+                color: pickup_color(pickup.kind),
+                custom_size: Some(Vec2::splat(2. * Pickup::RADIUS)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+}
+
+fn hide_collected_pickups(
+    mut pickup_query: Query<(&mut Visibility, Option<Ref<PickupCooldown>>), With<Pickup>>,
+    mut respawned: RemovedComponents<PickupCooldown>,
+) {
+    for (mut visibility, cooldown) in pickup_query.iter_mut() {
+        if cooldown.is_some_and(|cooldown| cooldown.is_added()) {
+            *visibility = Visibility::Hidden;
+        }
+    }
+    for entity in respawned.read() {
+        if let Ok((mut visibility, _)) = pickup_query.get_mut(entity) {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+#[derive(Component)]
+struct PickupEffect(Timer);
+
+fn spawn_pickup_effects(
+    mut commands: Commands,
+    mut pickup_events: EventReader<PickupCollected>,
+    textures: Res<SpriteAssets>,
+) {
+    for event in pickup_events.read() {
+        commands.spawn((
+            Name::new("Pickup Effect"),
+            SpriteBundle {
+                texture: textures.pickup.clone(),
+                transform: Transform::from_translation(event.position.extend(2.)),
+                sprite: Sprite {
+                    color: pickup_color(event.kind),
+                    custom_size: Some(Vec2::splat(2. * Pickup::RADIUS)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            PickupEffect(Timer::from_seconds(0.3, TimerMode::Once)),
+        ));
+    }
+}
+
+fn animate_pickup_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut effect_query: Query<(Entity, &mut Transform, &mut Sprite, &mut PickupEffect)>,
+) {
+    for (entity, mut transform, mut sprite, mut effect) in effect_query.iter_mut() {
+        effect.0.tick(time.delta());
+        let progress = effect.0.percent();
+        transform.scale = Vec3::splat(1. + progress);
+        sprite.color.set_a(1. - progress);
+        if effect.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 pub mod audio;
+pub mod gizmos;
 pub mod graphics;
 pub mod loading;
 
@@ -12,6 +13,7 @@ impl Plugin for FlavorPlugin {
             loading::AssetLoadingPlugin,
             audio::GameAudioPlugin,
             graphics::GameGraphicsPlugin,
+            gizmos::DebugGizmosPlugin,
         ));
     }
 }
@@ -35,10 +35,22 @@ impl Plugin for AssetLoadingPlugin {
 pub struct AudioAssets {
     #[asset(path = "audio/flying.ogg")]
     pub flying: Handle<AudioSource>,
+    // ToDo: replace with a dedicated sound
+    #[asset(path = "audio/flying.ogg")]
+    pub fire: Handle<AudioSource>,
+    // ToDo: replace with a dedicated sound
+    #[asset(path = "audio/flying.ogg")]
+    pub pickup: Handle<AudioSource>,
 }
 
 #[derive(AssetCollection, Resource)]
 pub struct SpriteAssets {
     #[asset(path = "textures/bevy.png")]
     pub character: Handle<Image>,
+    // ToDo: replace with a dedicated sprite
+    #[asset(path = "textures/bevy.png")]
+    pub projectile: Handle<Image>,
+    // ToDo: replace with a dedicated sprite
+    #[asset(path = "textures/bevy.png")]
+    pub pickup: Handle<Image>,
 }
@@ -1,37 +1,469 @@
-use bevy::prelude::*;
+#![allow(clippy::type_complexity)]
+
+use std::cmp::Ordering;
+
+use bevy::{ecs::schedule::SystemConfigs, prelude::*, time::Stopwatch};
 
 pub use bevy_game_blueprints as blueprints;
-use blueprints::{Character, CharacterMovement};
+use blueprints::{
+    Character, CharacterFacing, CharacterFire, CharacterMovement, Collider, Dead, ExitZone,
+    GameConfig, Health, InputSource, Lifetime, MovementContribution, MovementProfile,
+    MovementProfileSource, Pickup, PickupCollected, PickupCooldown, PickupKind, PickupRespawn,
+    Projectile, RollbackPlayer, Score, Velocity, Wall,
+};
+
+mod objectives;
+pub use objectives::*;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
 pub struct GameplaySet;
 
+// The parts of gameplay, each in a set of its own so they can be turned off one at a time,
+// such as from the editor
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
+pub enum GameplaySystem {
+    Movement,
+    Walls,
+    Projectiles,
+    Pickups,
+    Objectives,
+}
+
 pub struct GameplayPlugin;
 
 impl Plugin for GameplayPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, consume_actions.in_set(GameplaySet));
+        app.add_event::<PickupCollected>()
+            .add_event::<LevelOutcome>()
+            .add_systems(Update, gameplay_systems().in_set(GameplaySet));
 
         #[cfg(debug_assertions)]
         app.register_type::<Character>()
-            .register_type::<CharacterMovement>();
+            .register_type::<CharacterMovement>()
+            .register_type::<MovementContribution>()
+            .register_type::<InputSource>()
+            .register_type::<Velocity>()
+            .register_type::<MovementProfile>()
+            .register_type::<MovementProfileSource>()
+            .register_type::<CharacterFacing>()
+            .register_type::<CharacterFire>()
+            .register_type::<Health>()
+            .register_type::<Collider>()
+            .register_type::<Projectile>()
+            .register_type::<Lifetime>()
+            .register_type::<Score>()
+            .register_type::<Pickup>()
+            .register_type::<PickupCooldown>()
+            .register_type::<Dead>()
+            .register_type::<ExitZone>()
+            .register_type::<blueprints::Ghost>()
+            .register_type::<blueprints::GameCamera>()
+            .register_type::<blueprints::Terrain>()
+            .register_type::<Wall>()
+            .register_type::<LevelTimer>()
+            .register_type::<LevelObjective>()
+            .register_type::<LevelStats>();
+    }
+}
+
+// Every gameplay system in the order they run each tick
+// This lets other schedules, such as a rollback session, simulate gameplay as well
+pub fn gameplay_systems() -> SystemConfigs {
+    (
+        (
+            apply_movement_config.run_if(resource_exists::<GameConfig>()),
+            consume_actions,
+        )
+            .chain()
+            .in_set(GameplaySystem::Movement),
+        collide_with_walls.in_set(GameplaySystem::Walls),
+        (
+            fire_projectiles,
+            update_projectiles,
+            stop_projectiles_at_walls,
+        )
+            .chain()
+            .in_set(GameplaySystem::Projectiles),
+        (collect_pickups, respawn_pickups)
+            .chain()
+            .in_set(GameplaySystem::Pickups),
+        (
+            tick_level_timer.run_if(resource_exists::<LevelTimer>()),
+            objectives::kill_characters,
+            objectives::count_pickups.run_if(resource_exists::<LevelStats>()),
+            objectives::check_objective.run_if(resource_exists::<LevelObjective>()),
+        )
+            .chain()
+            .in_set(GameplaySystem::Objectives),
+    )
+        .chain()
+}
+
+// Measures how long the current level has been running. Insert it when a level starts.
+#[derive(Clone, Debug, Default)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct LevelTimer(pub Stopwatch);
+
+fn tick_level_timer(time: Res<Time>, mut timer: ResMut<LevelTimer>) {
+    timer.0.tick(time.delta());
+}
+
+// Runs on every tick so characters spawned after the config was loaded get it as well
+fn apply_movement_config(
+    config: Res<GameConfig>,
+    mut profile_query: Query<(Ref<MovementProfileSource>, &mut MovementProfile)>,
+) {
+    for (source, mut profile) in profile_query.iter_mut() {
+        let outdated = config.is_changed() || source.is_added();
+        if *source == MovementProfileSource::Config && outdated {
+            *profile = config.movement;
+        }
     }
 }
 
 fn consume_actions(
+    time: Res<Time>,
+    mut player_query: Query<(
+        &mut Transform,
+        &mut Velocity,
+        &MovementProfile,
+        Option<&mut CharacterMovement>,
+        Option<&mut CharacterFacing>,
+        Has<Dead>,
+    )>,
+) {
+    for (mut transform, mut velocity, profile, movement, facing, dead) in player_query.iter_mut() {
+        // Partial analog input asks for a proportionally slower speed
+        let intent = match movement {
+            Some(mut movement) => {
+                let intent = movement.resolve().clamp_length_max(1.);
+                movement.clear();
+                if dead {
+                    Vec2::ZERO
+                } else {
+                    intent
+                }
+            }
+            None => Vec2::ZERO,
+        };
+
+        velocity.0 = accelerate(
+            velocity.0,
+            intent * profile.max_speed,
+            profile,
+            time.delta_seconds(),
+        );
+        transform.translation += (velocity.0 * time.delta_seconds()).extend(0.);
+        if let Some(mut facing) = facing {
+            if intent != Vec2::ZERO {
+                facing.0 = intent.normalize();
+            }
+        }
+    }
+}
+
+// Characters slide along walls instead of moving through them
+fn collide_with_walls(
+    mut character_query: Query<(&mut Transform, &mut Velocity, &Collider), With<Character>>,
+    wall_query: Query<&Transform, (With<Wall>, Without<Character>)>,
+) {
+    for (mut transform, mut velocity, collider) in character_query.iter_mut() {
+        for wall_transform in wall_query.iter() {
+            let position = transform.translation.xy();
+            let Some(corrected) =
+                Wall::push_out(wall_transform.translation.xy(), position, collider.radius)
+            else {
+                continue;
+            };
+            // Drop the part of the velocity that points into the wall
+            let normal = (corrected - position).normalize_or_zero();
+            let into_wall = velocity.0.dot(normal).min(0.);
+            velocity.0 -= normal * into_wall;
+            transform.translation = corrected.extend(transform.translation.z);
+        }
+    }
+}
+
+// Steps the velocity towards the target velocity within the limits of the movement profile
+fn accelerate(velocity: Vec2, target: Vec2, profile: &MovementProfile, delta_seconds: f32) -> Vec2 {
+    let mut velocity = velocity;
+    if velocity != Vec2::ZERO && target != Vec2::ZERO {
+        let max_turn = profile.turn_rate * delta_seconds;
+        let turn = velocity.angle_between(target).clamp(-max_turn, max_turn);
+        velocity = Vec2::from_angle(turn).rotate(velocity);
+    }
+
+    let rate = if target.length_squared() > velocity.length_squared() {
+        profile.acceleration
+    } else {
+        profile.deceleration
+    };
+    let difference = target - velocity;
+    let step = rate * delta_seconds;
+    if difference.length() <= step {
+        target
+    } else {
+        velocity + difference.normalize() * step
+    }
+}
+
+fn fire_projectiles(
+    mut commands: Commands,
+    shooter_query: Query<(Entity, &Transform, &CharacterFacing, Has<Dead>), With<CharacterFire>>,
+) {
+    for (entity, transform, facing, dead) in shooter_query.iter() {
+        commands.entity(entity).remove::<CharacterFire>();
+        if !dead {
+            commands.spawn(Projectile::bundle(entity, transform.translation, facing.0));
+        }
+    }
+}
+
+fn update_projectiles(
     mut commands: Commands,
     time: Res<Time>,
-    mut player_query: Query<(Entity, &mut Transform, &CharacterMovement)>,
-) {
-    const SPEED: f32 = 150.;
-    for (entity, mut transform, movement) in player_query.iter_mut() {
-        let movement = movement.0.normalize();
-        let delta = Vec3::new(
-            movement.x * SPEED * time.delta_seconds(),
-            movement.y * SPEED * time.delta_seconds(),
-            0.,
+    mut projectile_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Lifetime,
+        &Projectile,
+        &Collider,
+    )>,
+    mut target_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Collider,
+            &mut Health,
+            Option<&RollbackPlayer>,
+        ),
+        (With<Character>, Without<Projectile>, Without<Dead>),
+    >,
+) {
+    for (entity, mut transform, mut lifetime, projectile, collider) in projectile_query.iter_mut() {
+        transform.translation +=
+            (projectile.direction * projectile.speed * time.delta_seconds()).extend(0.);
+
+        let position = transform.translation.xy();
+        let hit = target_query
+            .iter_mut()
+            .filter(|(target, ..)| *target != projectile.owner)
+            .filter(|(_, target_transform, target_collider, ..)| {
+                collider.overlaps(position, target_collider, target_transform.translation.xy())
+            })
+            .min_by(
+                |(a, a_transform, .., a_player), (b, b_transform, .., b_player)| {
+                    closest_first(
+                        position,
+                        (*a, a_transform, *a_player),
+                        (*b, b_transform, *b_player),
+                    )
+                },
+            );
+
+        if let Some((_, _, _, mut health, _)) = hit {
+            health.current = (health.current - projectile.damage).max(0.);
+            commands.entity(entity).despawn_recursive();
+        } else if lifetime.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn stop_projectiles_at_walls(
+    mut commands: Commands,
+    projectile_query: Query<(Entity, &Transform, &Collider), With<Projectile>>,
+    wall_query: Query<&Transform, With<Wall>>,
+) {
+    for (entity, transform, collider) in projectile_query.iter() {
+        let position = transform.translation.xy();
+        if wall_query.iter().any(|wall_transform| {
+            Wall::push_out(wall_transform.translation.xy(), position, collider.radius).is_some()
+        }) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// The entity and position of a character, and its player in a rollback session
+type Candidate<'a> = (Entity, &'a Transform, Option<&'a RollbackPlayer>);
+
+// Orders overlapping characters by distance, and then by player when they are equally close,
+// so every peer of a rollback session picks the same one. Each peer allocates its own entity ids,
+// so the entity only breaks ties between characters without a player, which only one world simulates.
+fn closest_first(
+    position: Vec2,
+    (a, a_transform, a_player): Candidate,
+    (b, b_transform, b_player): Candidate,
+) -> Ordering {
+    let a_distance = a_transform.translation.xy().distance_squared(position);
+    let b_distance = b_transform.translation.xy().distance_squared(position);
+    a_distance
+        .total_cmp(&b_distance)
+        .then(a_player.cmp(&b_player))
+        .then(a.cmp(&b))
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    mut pickup_events: EventWriter<PickupCollected>,
+    pickup_query: Query<(Entity, &Transform, &Collider, &Pickup), Without<PickupCooldown>>,
+    mut character_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Collider,
+            &mut Score,
+            &mut Health,
+            Option<&RollbackPlayer>,
+        ),
+        (With<Character>, Without<Dead>),
+    >,
+) {
+    for (pickup_entity, pickup_transform, pickup_collider, pickup) in pickup_query.iter() {
+        let position = pickup_transform.translation.xy();
+        let Some((character, _, _, mut score, mut health, _)) = character_query
+            .iter_mut()
+            .filter(|(_, transform, collider, ..)| {
+                pickup_collider.overlaps(position, collider, transform.translation.xy())
+            })
+            .min_by(
+                |(a, a_transform, .., a_player), (b, b_transform, .., b_player)| {
+                    closest_first(
+                        position,
+                        (*a, a_transform, *a_player),
+                        (*b, b_transform, *b_player),
+                    )
+                },
+            )
+        else {
+            continue;
+        };
+
+        score.0 += pickup.score;
+        if let PickupKind::Heal(amount) = pickup.kind {
+            health.current = (health.current + amount).min(health.max);
+        }
+        pickup_events.send(PickupCollected {
+            character,
+            pickup: pickup_entity,
+            kind: pickup.kind,
+            score: pickup.score,
+            position,
+        });
+
+        match pickup.respawn {
+            PickupRespawn::Never => {
+                commands.entity(pickup_entity).despawn_recursive();
+            }
+            PickupRespawn::After(delay) => {
+                commands
+                    .entity(pickup_entity)
+                    .insert(PickupCooldown(Timer::new(delay, TimerMode::Once)));
+            }
+        }
+    }
+}
+
+fn respawn_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut cooldown_query: Query<(Entity, &mut PickupCooldown)>,
+) {
+    for (entity, mut cooldown) in cooldown_query.iter_mut() {
+        if cooldown.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<PickupCooldown>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_first_breaks_ties_by_player() {
+        let position = Vec2::ZERO;
+        let left = Transform::from_xyz(-10., 0., 0.);
+        let right = Transform::from_xyz(10., 0., 0.);
+        let (a, b) = (Entity::from_raw(1), Entity::from_raw(2));
+        let (first, second) = (RollbackPlayer(0), RollbackPlayer(1));
+
+        // Equally close, so the lower player comes first in either order, whatever the entity ids
+        assert_eq!(
+            closest_first(
+                position,
+                (a, &left, Some(&second)),
+                (b, &right, Some(&first))
+            ),
+            Ordering::Greater
+        );
+        assert_eq!(
+            closest_first(
+                position,
+                (b, &right, Some(&first)),
+                (a, &left, Some(&second))
+            ),
+            Ordering::Less
+        );
+
+        // Characters without a player, outside a rollback session, fall back to the entity
+        assert_eq!(
+            closest_first(position, (a, &left, None), (b, &right, None)),
+            Ordering::Less
         );
-        transform.translation += delta;
-        commands.entity(entity).remove::<CharacterMovement>();
+        assert_eq!(
+            closest_first(position, (a, &left, None), (a, &left, None)),
+            Ordering::Equal
+        );
+
+        // Distance comes before the player
+        let near = Transform::from_xyz(5., 0., 0.);
+        assert_eq!(
+            closest_first(
+                position,
+                (a, &left, Some(&first)),
+                (b, &near, Some(&second))
+            ),
+            Ordering::Greater
+        );
+    }
+
+    fn profile(app: &App, entity: Entity) -> MovementProfile {
+        *app.world.get::<MovementProfile>(entity).unwrap()
+    }
+
+    #[test]
+    fn characters_spawned_after_a_config_change_follow_it() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<GameConfig>()
+            .add_plugins(GameplayPlugin);
+        let existing = app.world.spawn(Character::bundle()).id();
+        let own_profile = MovementProfile {
+            max_speed: 50.,
+            ..default()
+        };
+        let own = app
+            .world
+            .spawn(Character::bundle().with_movement_profile(own_profile))
+            .id();
+        app.update();
+
+        let config = MovementProfile {
+            max_speed: 400.,
+            ..default()
+        };
+        app.world.resource_mut::<GameConfig>().movement = config;
+        app.update();
+        assert_eq!(profile(&app, existing), config);
+        assert_eq!(profile(&app, own), own_profile);
+
+        // Spawned with the default profile, once the config changed
+        let spawned = app.world.spawn(Character::bundle()).id();
+        app.update();
+        assert_eq!(profile(&app, spawned), config);
+        assert_eq!(profile(&app, own), own_profile);
     }
 }
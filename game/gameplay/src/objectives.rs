@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    blueprints::{Character, Collider, Dead, ExitZone, Health, PickupCollected},
+    LevelTimer,
+};
+
+// The goal of the current level. Insert it when a level starts.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub enum LevelObjective {
+    // Any living character reaches an `ExitZone`
+    #[default]
+    ReachExit,
+    // The characters collect this many pickups in total
+    CollectItems(u32),
+    // Any character stays alive for this long
+    Survive(Duration),
+}
+
+// Running totals for the current level. Insert it when a level starts.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct LevelStats {
+    pub pickups: u32,
+    pub deaths: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Event)]
+pub enum LevelOutcome {
+    Won,
+    Lost,
+}
+
+pub(crate) fn kill_characters(
+    mut commands: Commands,
+    mut stats: Option<ResMut<LevelStats>>,
+    character_query: Query<(Entity, &Health), (With<Character>, Without<Dead>)>,
+) {
+    for (entity, health) in character_query.iter() {
+        if health.current <= 0. {
+            debug!("Character {:?} died", entity);
+            commands.entity(entity).insert(Dead);
+            if let Some(stats) = stats.as_mut() {
+                stats.deaths += 1;
+            }
+        }
+    }
+}
+
+pub(crate) fn count_pickups(
+    mut pickup_events: EventReader<PickupCollected>,
+    mut stats: ResMut<LevelStats>,
+) {
+    stats.pickups += pickup_events.read().count() as u32;
+}
+
+pub(crate) fn check_objective(
+    objective: Res<LevelObjective>,
+    timer: Option<Res<LevelTimer>>,
+    stats: Option<Res<LevelStats>>,
+    mut outcomes: EventWriter<LevelOutcome>,
+    character_query: Query<(&Transform, &Collider, Has<Dead>), With<Character>>,
+    exit_query: Query<(&Transform, &Collider), With<ExitZone>>,
+) {
+    if character_query.is_empty() {
+        return;
+    }
+    if character_query.iter().all(|(_, _, dead)| dead) {
+        outcomes.send(LevelOutcome::Lost);
+        return;
+    }
+
+    let won = match *objective {
+        LevelObjective::ReachExit => {
+            character_query
+                .iter()
+                .filter(|(_, _, dead)| !dead)
+                .any(|(transform, collider, _)| {
+                    exit_query.iter().any(|(exit_transform, exit_collider)| {
+                        collider.overlaps(
+                            transform.translation.xy(),
+                            exit_collider,
+                            exit_transform.translation.xy(),
+                        )
+                    })
+                })
+        }
+        LevelObjective::CollectItems(count) => stats.is_some_and(|stats| stats.pickups >= count),
+        LevelObjective::Survive(duration) => {
+            timer.is_some_and(|timer| timer.0.elapsed() >= duration)
+        }
+    };
+    if won {
+        outcomes.send(LevelOutcome::Won);
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io,
+    net::SocketAddr,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    blueprints::{Character, CharacterFire, CharacterMovement, Health},
+    transport::UdpTransport,
+    ClientId, ClientMessage, NetworkId, NetworkSet, NetworkSystem, ServerMessage,
+};
+
+// Replicated characters are drawn this far in the past, so there is usually a later snapshot to interpolate towards
+const INTERPOLATION_DELAY: f64 = 0.1;
+// How often `ClientMessage::Connect` is resent until the server answers
+const CONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct NetworkClientPlugin;
+
+/// This plugin exchanges messages with a server while the [`ClientConnection`] resource exists
+/// The movement and fire inputs of [`LocalInput`] entities are sent to the server, and the
+/// characters it replicates are spawned, interpolated and despawned locally
+impl Plugin for NetworkClientPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            receive_server_messages
+                .in_set(NetworkSet)
+                .in_set(NetworkSystem::Client)
+                .run_if(resource_exists::<ClientConnection>()),
+        )
+        .add_systems(
+            Update,
+            (send_inputs, interpolate_replicated_characters)
+                .in_set(NetworkSet)
+                .in_set(NetworkSystem::Client)
+                .run_if(resource_exists::<ClientConnection>()),
+        );
+
+        #[cfg(debug_assertions)]
+        app.register_type::<NetworkId>()
+            .register_type::<ClientId>()
+            .register_type::<LocalInput>();
+    }
+}
+
+// Marks the entity whose `CharacterMovement` and `CharacterFire` are sent to the server
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+pub struct LocalInput;
+
+#[derive(Resource)]
+pub struct ClientConnection {
+    transport: UdpTransport,
+    server: SocketAddr,
+    client: Option<ClientId>,
+    sequence: u32,
+    connect_timer: Timer,
+    last_snapshot: f64,
+    // The estimated difference between the server clock and the local clock
+    clock_offset: Option<f64>,
+    entities: HashMap<NetworkId, Entity>,
+}
+
+impl ClientConnection {
+    pub fn connect(server: SocketAddr) -> io::Result<Self> {
+        let transport = match server {
+            SocketAddr::V4(_) => UdpTransport::bind("0.0.0.0:0"),
+            SocketAddr::V6(_) => UdpTransport::bind("[::]:0"),
+        }?;
+        transport.send(server, &ClientMessage::Connect);
+        Ok(ClientConnection {
+            transport,
+            server,
+            client: None,
+            sequence: 0,
+            connect_timer: Timer::new(CONNECT_INTERVAL, TimerMode::Repeating),
+            last_snapshot: f64::NEG_INFINITY,
+            clock_offset: None,
+            entities: HashMap::default(),
+        })
+    }
+
+    pub fn server(&self) -> SocketAddr {
+        self.server
+    }
+
+    // `None` until the server accepted the connection
+    pub fn client(&self) -> Option<ClientId> {
+        self.client
+    }
+
+    pub fn disconnect(&self) {
+        self.transport.send(self.server, &ClientMessage::Disconnect);
+    }
+}
+
+// Received positions of a replicated character, stamped with the server time
+#[derive(Debug, Default)]
+#[derive(Component)]
+struct InterpolationBuffer(VecDeque<(f64, Vec2)>);
+
+fn receive_server_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut connection: ResMut<ClientConnection>,
+    mut character_query: Query<(&mut InterpolationBuffer, &mut Health)>,
+) {
+    let server = connection.server;
+    for (address, message) in connection.transport.receive::<ServerMessage>() {
+        if address != server {
+            continue;
+        }
+        match message {
+            ServerMessage::Welcome { client } => {
+                if connection.client.is_none() {
+                    info!("Connected to {server} as {:?}", client);
+                    connection.client = Some(client);
+                }
+            }
+            ServerMessage::Snapshot {
+                time: server_time,
+                characters,
+            } => {
+                // Snapshots that arrive out of order are stale
+                if server_time <= connection.last_snapshot {
+                    continue;
+                }
+                connection.last_snapshot = server_time;
+                let offset = server_time - time.elapsed_seconds_f64();
+                connection.clock_offset = Some(match connection.clock_offset {
+                    Some(previous) => previous + (offset - previous) * 0.1,
+                    None => offset,
+                });
+
+                let mut replicated = HashSet::new();
+                for state in characters {
+                    replicated.insert(state.id);
+                    match connection.entities.get(&state.id) {
+                        Some(entity) => {
+                            if let Ok((mut buffer, mut health)) = character_query.get_mut(*entity) {
+                                buffer.0.push_back((server_time, state.translation));
+                                health.current = state.health;
+                            }
+                        }
+                        None => {
+                            // Without `Velocity` and `MovementProfile` the local gameplay systems leave it alone
+                            let entity = commands
+                                .spawn((
+                                    Name::new("Replicated Character"),
+                                    Character,
+                                    Health {
+                                        current: state.health,
+                                        max: state.max_health,
+                                    },
+                                    SpatialBundle::from_transform(Transform::from_translation(
+                                        state.translation.extend(0.),
+                                    )),
+                                    InterpolationBuffer(VecDeque::from([(
+                                        server_time,
+                                        state.translation,
+                                    )])),
+                                    state.id,
+                                    state.owner,
+                                ))
+                                .id();
+                            connection.entities.insert(state.id, entity);
+                        }
+                    }
+                }
+                connection.entities.retain(|id, entity| {
+                    let keep = replicated.contains(id);
+                    if !keep {
+                        commands.entity(*entity).despawn_recursive();
+                    }
+                    keep
+                });
+            }
+        }
+    }
+}
+
+fn send_inputs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut connection: ResMut<ClientConnection>,
+    mut input_query: Query<(Entity, &mut CharacterMovement, Has<CharacterFire>), With<LocalInput>>,
+) {
+    if connection.client.is_none() {
+        if connection.connect_timer.tick(time.delta()).just_finished() {
+            connection
+                .transport
+                .send(connection.server, &ClientMessage::Connect);
+        }
+        return;
+    }
+    for (entity, mut movement, fire) in input_query.iter_mut() {
+        connection.sequence += 1;
+        let message = ClientMessage::Input {
+            sequence: connection.sequence,
+            movement: movement.resolve(),
+            fire,
+        };
+        connection.transport.send(connection.server, &message);
+        movement.clear();
+        if fire {
+            commands.entity(entity).remove::<CharacterFire>();
+        }
+    }
+}
+
+fn interpolate_replicated_characters(
+    time: Res<Time>,
+    connection: Res<ClientConnection>,
+    mut character_query: Query<(&mut Transform, &mut InterpolationBuffer)>,
+) {
+    let Some(clock_offset) = connection.clock_offset else {
+        return;
+    };
+    let render_time = time.elapsed_seconds_f64() + clock_offset - INTERPOLATION_DELAY;
+    for (mut transform, mut buffer) in character_query.iter_mut() {
+        // Keep one sample at or before the render time to interpolate from
+        while buffer.0.len() > 1 && buffer.0[1].0 <= render_time {
+            buffer.0.pop_front();
+        }
+        let position = match (buffer.0.front(), buffer.0.get(1)) {
+            (Some((from_time, from)), Some((to_time, to))) if *from_time <= render_time => {
+                let t = (render_time - from_time) / (to_time - from_time);
+                from.lerp(*to, t.clamp(0., 1.) as f32)
+            }
+            (Some((_, position)), _) => *position,
+            (None, _) => continue,
+        };
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
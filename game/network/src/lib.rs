@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+pub use bevy_game_blueprints as blueprints;
+use bevy_game_gameplay as gameplay;
+
+mod client;
+pub use client::*;
+mod protocol;
+pub use protocol::*;
+mod rollback;
+pub use rollback::*;
+mod server;
+pub use server::*;
+mod transport;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
+pub struct NetworkSet;
+
+// The parts of `NetworkSet`, so they can be turned off one at a time
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
+pub enum NetworkSystem {
+    Client,
+    Server,
+    Rollback,
+}
+
+// The port used when no address is given
+pub const DEFAULT_PORT: u16 = 5737;
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Identifies a replicated entity on the server and every client
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Component, Reflect)]
+#[derive(Serialize, Deserialize)]
+pub struct NetworkId(pub u64);
+
+// Identifies a connected client, and the character it controls
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Component, Reflect)]
+#[derive(Serialize, Deserialize)]
+pub struct ClientId(pub u64);
+
+#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize)]
+pub enum ClientMessage {
+    // Sent until the server answers with `ServerMessage::Welcome`
+    Connect,
+    // The latest input of the client's player, sent every frame
+    // Older sequence numbers are ignored in case packets arrive out of order
+    Input {
+        sequence: u32,
+        movement: Vec2,
+        fire: bool,
+    },
+    Disconnect,
+}
+
+#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize)]
+pub enum ServerMessage {
+    Welcome {
+        client: ClientId,
+    },
+    // The state of every character, stamped with the elapsed server time in seconds
+    Snapshot {
+        time: f64,
+        characters: Vec<CharacterState>,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct CharacterState {
+    pub id: NetworkId,
+    pub owner: ClientId,
+    pub translation: Vec2,
+    pub health: f32,
+    pub max_health: f32,
+}
+
+// The movement and fire input of one player for one rollback frame
+// Movement is quantized to a signed byte per axis, so every peer simulates exactly the same value
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize)]
+pub struct PackedInput(pub u32);
+
+impl PackedInput {
+    const FIRE: u32 = 1 << 16;
+
+    pub fn pack(movement: Vec2, fire: bool) -> Self {
+        let quantize = |axis: f32| (axis.clamp(-1., 1.) * 127.).round() as i8 as u8 as u32;
+        let fire = if fire { Self::FIRE } else { 0 };
+        PackedInput(quantize(movement.x) | quantize(movement.y) << 8 | fire)
+    }
+
+    pub fn movement(&self) -> Vec2 {
+        let axis = |byte: u32| (byte as u8 as i8) as f32 / 127.;
+        Vec2::new(axis(self.0 & 0xff), axis(self.0 >> 8 & 0xff))
+    }
+
+    pub fn fire(&self) -> bool {
+        self.0 & Self::FIRE != 0
+    }
+
+    // Inputs are assumed to repeat in later frames, except for presses which are one-off
+    pub fn predict(&self) -> Self {
+        PackedInput(self.0 & !Self::FIRE)
+    }
+}
+
+// Exchanged between rollback peers every frame
+// Every input from the first frame the peer still needs is resent, so lost packets are recovered
+#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct PeerInputs {
+    // The next frame the sender needs inputs for
+    pub ack: u32,
+    pub start_frame: u32,
+    pub inputs: Vec<PackedInput>,
+}
@@ -0,0 +1,880 @@
+use std::{
+    any::Any,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    io,
+    net::SocketAddr,
+    time::Duration,
+};
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        schedule::ScheduleLabel,
+        world::EntityRef,
+    },
+    prelude::*,
+};
+
+use crate::{
+    blueprints::{
+        Character, CharacterFacing, CharacterFire, CharacterMovement, Collider, Dead, Health,
+        InputSource, Lifetime, MovementProfile, MovementProfileSource, Pickup, PickupCollected,
+        PickupCooldown, Projectile, RollbackPlayer, Score, Velocity,
+    },
+    gameplay::{gameplay_systems, GameplaySet, LevelOutcome, LevelStats, LevelTimer},
+    transport::UdpTransport,
+    NetworkSet, NetworkSystem, PackedInput, PeerInputs,
+};
+
+// Rollback sessions simulate gameplay at this fixed rate
+pub const ROLLBACK_FPS: u32 = 60;
+const FRAME_DURATION: Duration = Duration::from_nanos(1_000_000_000 / ROLLBACK_FPS as u64);
+// How far the local simulation may run ahead of the last confirmed remote input
+const MAX_PREDICTION_FRAMES: u32 = 8;
+// Limits how many frames are simulated to catch up after a slow update
+const MAX_FRAMES_PER_UPDATE: u32 = 4;
+
+// Gameplay systems run in this schedule while a `RollbackSession` exists, instead of `Update`
+#[derive(Clone, Debug, PartialEq, Eq, Hash, ScheduleLabel)]
+pub struct RollbackSchedule;
+
+pub struct RollbackPlugin;
+
+/// This plugin simulates gameplay in fixed frames while the [`RollbackSession`] resource exists
+/// Entities with a `Character`, `Projectile` or `Pickup` are snapshotted before every frame,
+/// using the components and resources registered with [`RollbackApp`].
+/// Events registered with [`RollbackApp`] reach the rest of the app once for each frame,
+/// after the frame is confirmed and will not be simulated again.
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DesyncDetected>()
+            .init_resource::<RollbackRegistry>()
+            .add_systems(RollbackSchedule, gameplay_systems())
+            .configure_sets(
+                Update,
+                GameplaySet.run_if(not(resource_exists::<RollbackSession>())),
+            )
+            .add_systems(
+                Update,
+                advance_rollback
+                    .in_set(NetworkSet)
+                    .in_set(NetworkSystem::Rollback)
+                    .run_if(resource_exists::<RollbackSession>()),
+            )
+            .rollback_component::<Name>()
+            .rollback_component::<Transform>()
+            .rollback_component::<Character>()
+            .rollback_component::<Velocity>()
+            .rollback_component::<MovementProfile>()
+            .rollback_component::<MovementProfileSource>()
+            .rollback_component::<CharacterFacing>()
+            .rollback_component::<Health>()
+            .rollback_component::<Score>()
+            .rollback_component::<Collider>()
+            .rollback_component::<Dead>()
+            .rollback_mapped_component::<Projectile>()
+            .rollback_component::<Lifetime>()
+            .rollback_component::<Pickup>()
+            .rollback_component::<PickupCooldown>()
+            .rollback_resource::<LevelTimer>()
+            .rollback_resource::<LevelStats>()
+            .rollback_event::<PickupCollected>()
+            .rollback_event::<LevelOutcome>();
+
+        #[cfg(debug_assertions)]
+        app.register_type::<RollbackPlayer>();
+    }
+}
+
+// Marks the entities that are snapshotted and restored
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component)]
+pub struct Rollback;
+
+// Sent when a sync-test re-simulation ends up in a different state than the first simulation
+#[derive(Clone, Copy, Debug)]
+#[derive(Event)]
+pub struct DesyncDetected {
+    pub frame: u32,
+}
+
+type Saved = Box<dyn Any + Send + Sync>;
+
+#[derive(Clone, Copy)]
+struct RollbackComponent {
+    save: fn(&EntityRef) -> Option<Saved>,
+    load: fn(&mut EntityWorldMut, Option<&Saved>),
+    hash: fn(&Saved, &mut DefaultHasher),
+    // Points the entities the component refers to at the ids they have after a rollback
+    map_entities: Option<fn(&mut World, Entity, &mut EntityMapper)>,
+}
+
+#[derive(Clone, Copy)]
+struct RollbackResource {
+    save: fn(&World) -> Option<Saved>,
+    load: fn(&mut World, Option<&Saved>),
+    hash: fn(&Saved, &mut DefaultHasher),
+}
+
+#[derive(Clone, Copy)]
+struct RollbackEvent {
+    // Swaps the events of the app with the ones gameplay sends and reads while simulating frames
+    swap: fn(&mut World),
+    // Takes the events sent while a frame was simulated
+    drain: fn(&mut World) -> Saved,
+    send: fn(&mut World, Saved),
+}
+
+// The events gameplay sends and reads while simulating frames, kept apart from the ones
+// the rest of the app reads
+#[derive(Resource)]
+struct SimulatedEvents<E: Event>(Events<E>);
+
+impl<E: Event> Default for SimulatedEvents<E> {
+    fn default() -> Self {
+        SimulatedEvents(Events::default())
+    }
+}
+
+// The components and resources that make up the rolled back gameplay state,
+// and the events gameplay sends while simulating it
+#[derive(Clone, Default)]
+#[derive(Resource)]
+pub struct RollbackRegistry {
+    components: Vec<RollbackComponent>,
+    resources: Vec<RollbackResource>,
+    events: Vec<RollbackEvent>,
+}
+
+impl RollbackRegistry {
+    fn swap_events(&self, world: &mut World) {
+        for event in self.events.iter() {
+            (event.swap)(world);
+        }
+    }
+
+    fn drain_events(&self, world: &mut World) -> Vec<Saved> {
+        self.events
+            .iter()
+            .map(|event| (event.drain)(world))
+            .collect()
+    }
+
+    fn send_events(&self, world: &mut World, events: Vec<Saved>) {
+        for (event, saved) in self.events.iter().zip(events) {
+            (event.send)(world, saved);
+        }
+    }
+}
+
+pub trait RollbackApp {
+    // Components are compared through their `Debug` output when checking for desyncs
+    fn rollback_component<C: Component + Clone + Debug>(&mut self) -> &mut Self;
+    // Entities despawned since a snapshot come back with new ids when it is restored,
+    // so components that refer to other entities are mapped to the new ones
+    fn rollback_mapped_component<C: Component + Clone + Debug + MapEntities>(
+        &mut self,
+    ) -> &mut Self;
+    fn rollback_resource<R: Resource + Clone + Debug>(&mut self) -> &mut Self;
+    // Gameplay systems still read the events of the frame they were sent in,
+    // but other systems only read them once, after the frame is confirmed
+    fn rollback_event<E: Event>(&mut self) -> &mut Self;
+}
+
+impl RollbackApp for App {
+    fn rollback_component<C: Component + Clone + Debug>(&mut self) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(RollbackRegistry::default)
+            .components
+            .push(RollbackComponent {
+                save: save_component::<C>,
+                load: load_component::<C>,
+                hash: hash_saved::<C>,
+                map_entities: None,
+            });
+        self
+    }
+
+    fn rollback_mapped_component<C: Component + Clone + Debug + MapEntities>(
+        &mut self,
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(RollbackRegistry::default)
+            .components
+            .push(RollbackComponent {
+                save: save_component::<C>,
+                load: load_component::<C>,
+                hash: hash_saved::<C>,
+                map_entities: Some(map_component_entities::<C>),
+            });
+        self
+    }
+
+    fn rollback_resource<R: Resource + Clone + Debug>(&mut self) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(RollbackRegistry::default)
+            .resources
+            .push(RollbackResource {
+                save: save_resource::<R>,
+                load: load_resource::<R>,
+                hash: hash_saved::<R>,
+            });
+        self
+    }
+
+    fn rollback_event<E: Event>(&mut self) -> &mut Self {
+        self.init_resource::<SimulatedEvents<E>>();
+        self.world
+            .get_resource_or_insert_with(RollbackRegistry::default)
+            .events
+            .push(RollbackEvent {
+                swap: swap_events::<E>,
+                drain: drain_events::<E>,
+                send: send_events::<E>,
+            });
+        self
+    }
+}
+
+fn save_component<C: Component + Clone>(entity: &EntityRef) -> Option<Saved> {
+    entity
+        .get::<C>()
+        .map(|component| Box::new(component.clone()) as Saved)
+}
+
+fn load_component<C: Component + Clone>(entity: &mut EntityWorldMut, saved: Option<&Saved>) {
+    match saved.and_then(|saved| saved.downcast_ref::<C>()) {
+        Some(component) => {
+            entity.insert(component.clone());
+        }
+        None => {
+            entity.remove::<C>();
+        }
+    }
+}
+
+fn map_component_entities<C: Component + MapEntities>(
+    world: &mut World,
+    entity: Entity,
+    entity_mapper: &mut EntityMapper,
+) {
+    if let Some(mut component) = world.get_mut::<C>(entity) {
+        component.map_entities(entity_mapper);
+    }
+}
+
+fn save_resource<R: Resource + Clone>(world: &World) -> Option<Saved> {
+    world
+        .get_resource::<R>()
+        .map(|resource| Box::new(resource.clone()) as Saved)
+}
+
+fn load_resource<R: Resource + Clone>(world: &mut World, saved: Option<&Saved>) {
+    match saved.and_then(|saved| saved.downcast_ref::<R>()) {
+        Some(resource) => world.insert_resource(resource.clone()),
+        None => {
+            world.remove_resource::<R>();
+        }
+    }
+}
+
+fn swap_events<E: Event>(world: &mut World) {
+    world.resource_scope(|world, mut simulated: Mut<SimulatedEvents<E>>| {
+        std::mem::swap(&mut *world.resource_mut::<Events<E>>(), &mut simulated.0);
+    });
+}
+
+fn drain_events<E: Event>(world: &mut World) -> Saved {
+    let events: Vec<E> = world
+        .resource_mut::<SimulatedEvents<E>>()
+        .0
+        .drain()
+        .collect();
+    Box::new(events)
+}
+
+fn send_events<E: Event>(world: &mut World, saved: Saved) {
+    if let Ok(events) = saved.downcast::<Vec<E>>() {
+        world.resource_mut::<Events<E>>().extend(*events);
+    }
+}
+
+fn hash_saved<T: Debug + 'static>(saved: &Saved, hasher: &mut DefaultHasher) {
+    if let Some(value) = saved.downcast_ref::<T>() {
+        format!("{value:?}").hash(hasher);
+    }
+}
+
+// The rolled back state of the world before a frame was simulated
+struct WorldSnapshot {
+    entities: Vec<(Entity, Vec<Option<Saved>>)>,
+    resources: Vec<Option<Saved>>,
+}
+
+impl WorldSnapshot {
+    fn save(world: &mut World, registry: &RollbackRegistry) -> Self {
+        let entities = rollback_entities(world)
+            .into_iter()
+            .map(|entity| {
+                let entity_ref = world.entity(entity);
+                let components = registry
+                    .components
+                    .iter()
+                    .map(|component| (component.save)(&entity_ref))
+                    .collect();
+                (entity, components)
+            })
+            .collect();
+        let resources = registry
+            .resources
+            .iter()
+            .map(|resource| (resource.save)(world))
+            .collect();
+        WorldSnapshot {
+            entities,
+            resources,
+        }
+    }
+
+    fn restore(&self, world: &mut World, registry: &RollbackRegistry) {
+        let saved: HashSet<Entity> = self.entities.iter().map(|(entity, _)| *entity).collect();
+        for entity in rollback_entities(world) {
+            if !saved.contains(&entity) {
+                world.entity_mut(entity).despawn_recursive();
+            }
+        }
+        // The id every saved entity has after the restore, in the map type `EntityMapper` uses
+        let mut entity_map = bevy::utils::HashMap::default();
+        for (saved_entity, components) in self.entities.iter() {
+            // Entities despawned since the snapshot come back with a new id
+            let mut entity = if world.get_entity(*saved_entity).is_some() {
+                world.entity_mut(*saved_entity)
+            } else {
+                world.spawn((
+                    Rollback,
+                    GlobalTransform::default(),
+                    VisibilityBundle::default(),
+                ))
+            };
+            for (component, saved) in registry.components.iter().zip(components.iter()) {
+                (component.load)(&mut entity, saved.as_ref());
+            }
+            entity_map.insert(*saved_entity, entity.id());
+        }
+        let mapped: Vec<_> = registry
+            .components
+            .iter()
+            .filter_map(|component| component.map_entities)
+            .collect();
+        if !mapped.is_empty() {
+            let entities: Vec<Entity> = entity_map.values().copied().collect();
+            EntityMapper::world_scope(&mut entity_map, world, |world, entity_mapper| {
+                for entity in entities {
+                    for map_entities in mapped.iter() {
+                        map_entities(world, entity, entity_mapper);
+                    }
+                }
+            });
+        }
+        for (resource, saved) in registry.resources.iter().zip(self.resources.iter()) {
+            (resource.load)(world, saved.as_ref());
+        }
+    }
+
+    fn checksum(&self, registry: &RollbackRegistry) -> u64 {
+        // Entity ids and iteration order can change across a rollback, so entities are combined
+        // in a way that does not depend on either
+        let mut checksum = 0u64;
+        for (_, components) in self.entities.iter() {
+            let mut hasher = DefaultHasher::new();
+            hash_all(
+                registry.components.iter().map(|component| component.hash),
+                components,
+                &mut hasher,
+            );
+            checksum = checksum.wrapping_add(hasher.finish());
+        }
+        let mut hasher = DefaultHasher::new();
+        hash_all(
+            registry.resources.iter().map(|resource| resource.hash),
+            &self.resources,
+            &mut hasher,
+        );
+        checksum.wrapping_add(hasher.finish())
+    }
+}
+
+fn hash_all(
+    hashes: impl Iterator<Item = fn(&Saved, &mut DefaultHasher)>,
+    values: &[Option<Saved>],
+    hasher: &mut DefaultHasher,
+) {
+    for (hash, value) in hashes.zip(values.iter()) {
+        match value {
+            Some(value) => hash(value, hasher),
+            None => 0u8.hash(hasher),
+        }
+    }
+}
+
+fn rollback_entities(world: &mut World) -> Vec<Entity> {
+    world
+        .query_filtered::<Entity, With<Rollback>>()
+        .iter(world)
+        .collect()
+}
+
+// Gameplay spawns these during frames, so they are marked after every frame
+fn mark_rollback_entities(world: &mut World) {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, (
+            Or<(With<Character>, With<Projectile>, With<Pickup>)>,
+            Without<Rollback>,
+        )>()
+        .iter(world)
+        .collect();
+    for entity in entities {
+        world.entity_mut(entity).insert(Rollback);
+    }
+}
+
+pub enum RollbackMode {
+    // Every frame, the last `check_distance` frames are restored and simulated again,
+    // and any difference in the resulting state is reported with `DesyncDetected`
+    SyncTest {
+        check_distance: u32,
+    },
+    // Inputs are exchanged with a peer, whose missing inputs are predicted and corrected later
+    PeerToPeer {
+        local_player: usize,
+        peer: SocketAddr,
+    },
+}
+
+#[derive(Resource)]
+pub struct RollbackSession {
+    mode: RollbackMode,
+    transport: Option<UdpTransport>,
+    players: usize,
+    // The next frame to simulate
+    frame: u32,
+    accumulator: Duration,
+    // The latest inputs of local players, used for every frame until they are read again
+    local_inputs: Vec<PackedInput>,
+    // Inputs that are known for certain, per frame and player
+    confirmed: HashMap<u32, Vec<Option<PackedInput>>>,
+    // The inputs each frame was last simulated with, including predictions
+    simulated: HashMap<u32, Vec<PackedInput>>,
+    snapshots: VecDeque<(u32, WorldSnapshot)>,
+    checksums: HashMap<u32, u64>,
+    // The events sent when each frame was last simulated, until the frame is confirmed
+    frame_events: HashMap<u32, Vec<Saved>>,
+    // The first frame whose events have not been sent to the rest of the app
+    events_frame: u32,
+    // The first frame without a confirmed input from the peer
+    remote_frame: u32,
+    // The first frame the peer has not confirmed our input for
+    peer_frame: u32,
+    // The earliest frame whose confirmed input arrived since the last update
+    received_frame: Option<u32>,
+}
+
+impl RollbackSession {
+    fn new(mode: RollbackMode, transport: Option<UdpTransport>, players: usize) -> Self {
+        RollbackSession {
+            mode,
+            transport,
+            players,
+            frame: 0,
+            accumulator: Duration::ZERO,
+            local_inputs: vec![PackedInput::default(); players],
+            confirmed: HashMap::default(),
+            simulated: HashMap::default(),
+            snapshots: VecDeque::default(),
+            checksums: HashMap::default(),
+            frame_events: HashMap::default(),
+            events_frame: 0,
+            remote_frame: 0,
+            peer_frame: 0,
+            received_frame: None,
+        }
+    }
+
+    pub fn sync_test(players: usize, check_distance: u32) -> Self {
+        RollbackSession::new(RollbackMode::SyncTest { check_distance }, None, players)
+    }
+
+    // The player with the lower address is player one, so both peers agree without negotiating
+    pub fn peer_to_peer(local: SocketAddr, peer: SocketAddr) -> io::Result<Self> {
+        let transport = UdpTransport::bind(local)?;
+        let local_player = if local < peer { 0 } else { 1 };
+        Ok(RollbackSession::new(
+            RollbackMode::PeerToPeer { local_player, peer },
+            Some(transport),
+            2,
+        ))
+    }
+
+    pub fn mode(&self) -> &RollbackMode {
+        &self.mode
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    fn is_local(&self, player: usize) -> bool {
+        match self.mode {
+            RollbackMode::SyncTest { .. } => true,
+            RollbackMode::PeerToPeer { local_player, .. } => player == local_player,
+        }
+    }
+
+    // Frames before this one are simulated with confirmed inputs, and are never simulated again
+    fn confirmed_frame(&self) -> u32 {
+        match self.mode {
+            RollbackMode::SyncTest { .. } => self.frame,
+            RollbackMode::PeerToPeer { .. } => self.remote_frame.min(self.frame),
+        }
+    }
+
+    fn can_advance(&self) -> bool {
+        match self.mode {
+            RollbackMode::SyncTest { .. } => true,
+            RollbackMode::PeerToPeer { .. } => {
+                self.frame < self.remote_frame + MAX_PREDICTION_FRAMES
+            }
+        }
+    }
+
+    // Confirmed inputs where known, otherwise the last confirmed input of that player repeated
+    fn inputs_for(&self, frame: u32) -> Vec<PackedInput> {
+        (0..self.players)
+            .map(|player| {
+                let confirmed =
+                    |frame: u32| self.confirmed.get(&frame).and_then(|inputs| inputs[player]);
+                confirmed(frame).unwrap_or_else(|| {
+                    (0..frame)
+                        .rev()
+                        .take(MAX_PREDICTION_FRAMES as usize)
+                        .find_map(confirmed)
+                        .map(|input| input.predict())
+                        .unwrap_or_default()
+                })
+            })
+            .collect()
+    }
+
+    fn confirm(&mut self, frame: u32, player: usize, input: PackedInput) {
+        let players = self.players;
+        let inputs = self
+            .confirmed
+            .entry(frame)
+            .or_insert_with(|| vec![None; players]);
+        if inputs[player].is_none() {
+            inputs[player] = Some(input);
+            if !self.is_local(player) {
+                self.received_frame = Some(self.received_frame.map_or(frame, |f| f.min(frame)));
+            }
+        }
+    }
+
+    fn receive_peer_inputs(&mut self) {
+        let RollbackMode::PeerToPeer { local_player, peer } = self.mode else {
+            return;
+        };
+        let Some(transport) = self.transport.as_mut() else {
+            return;
+        };
+        let remote_player = 1 - local_player;
+        for (address, message) in transport.receive::<PeerInputs>() {
+            if address != peer {
+                continue;
+            }
+            self.peer_frame = self.peer_frame.max(message.ack);
+            for (frame, input) in (message.start_frame..).zip(message.inputs) {
+                self.confirm(frame, remote_player, input);
+            }
+        }
+        while self
+            .confirmed
+            .get(&self.remote_frame)
+            .is_some_and(|inputs| inputs[remote_player].is_some())
+        {
+            self.remote_frame += 1;
+        }
+    }
+
+    fn send_peer_inputs(&self) {
+        let (RollbackMode::PeerToPeer { local_player, peer }, Some(transport)) =
+            (&self.mode, &self.transport)
+        else {
+            return;
+        };
+        let inputs = (self.peer_frame..self.frame)
+            .map_while(|frame| {
+                self.confirmed
+                    .get(&frame)
+                    .and_then(|inputs| inputs[*local_player])
+            })
+            .collect();
+        transport.send(
+            *peer,
+            &PeerInputs {
+                ack: self.remote_frame,
+                start_frame: self.peer_frame,
+                inputs,
+            },
+        );
+    }
+
+    // Reads and clears the inputs queued on every player's character
+    // Presses are kept until a frame is simulated with them
+    fn gather_local_inputs(&mut self, world: &mut World) {
+        let mut fired = Vec::new();
+        let mut player_query = world.query::<(
+            Entity,
+            &RollbackPlayer,
+            &mut CharacterMovement,
+            Has<CharacterFire>,
+        )>();
+        for (entity, player, mut movement, fire) in player_query.iter_mut(world) {
+            if let Some(input) = self.local_inputs.get_mut(player.0) {
+                *input = PackedInput::pack(movement.resolve(), fire || input.fire());
+            }
+            movement.clear();
+            if fire {
+                fired.push(entity);
+            }
+        }
+        for entity in fired {
+            world.entity_mut(entity).remove::<CharacterFire>();
+        }
+    }
+
+    // Only local players' inputs are used, the others come from the peer
+    fn confirm_local_inputs(&mut self) {
+        for player in 0..self.players {
+            if self.is_local(player) {
+                let input = self.local_inputs[player];
+                self.confirm(self.frame, player, input);
+                self.local_inputs[player] = input.predict();
+            }
+        }
+    }
+
+    fn save_snapshot(&mut self, world: &mut World, registry: &RollbackRegistry) {
+        // The level may have spawned entities since the last frame
+        mark_rollback_entities(world);
+        let snapshot = WorldSnapshot::save(world, registry);
+        self.snapshots.retain(|(frame, _)| *frame != self.frame);
+        self.snapshots.push_back((self.frame, snapshot));
+    }
+
+    fn restore_snapshot(&self, world: &mut World, registry: &RollbackRegistry, frame: u32) -> bool {
+        match self.snapshots.iter().find(|(saved, _)| *saved == frame) {
+            Some((_, snapshot)) => {
+                snapshot.restore(world, registry);
+                true
+            }
+            None => {
+                error!("No rollback snapshot for frame {frame}");
+                false
+            }
+        }
+    }
+
+    fn advance_frame(&mut self, world: &mut World, registry: &RollbackRegistry) {
+        self.save_snapshot(world, registry);
+        let inputs = self.inputs_for(self.frame);
+        simulate_frame(world, registry, self.frame, &inputs);
+        self.simulated.insert(self.frame, inputs);
+        // Replaces the events of an earlier simulation of the frame
+        self.frame_events
+            .insert(self.frame, registry.drain_events(world));
+        self.frame += 1;
+    }
+
+    fn send_confirmed_events(&mut self, world: &mut World, registry: &RollbackRegistry) {
+        while self.events_frame < self.confirmed_frame() {
+            if let Some(events) = self.frame_events.remove(&self.events_frame) {
+                registry.send_events(world, events);
+            }
+            self.events_frame += 1;
+        }
+    }
+
+    // Restores the state before `frame` and simulates up to the current frame again
+    fn resimulate_from(&mut self, world: &mut World, registry: &RollbackRegistry, frame: u32) {
+        if !self.restore_snapshot(world, registry, frame) {
+            return;
+        }
+        let current = self.frame;
+        self.frame = frame;
+        while self.frame < current {
+            self.advance_frame(world, registry);
+        }
+    }
+
+    fn check_sync(&mut self, world: &mut World, registry: &RollbackRegistry) {
+        let RollbackMode::SyncTest { check_distance } = self.mode else {
+            return;
+        };
+        let last = self.frame - 1;
+        self.checksums.insert(
+            last,
+            WorldSnapshot::save(world, registry).checksum(registry),
+        );
+        let Some(first) = last.checked_sub(check_distance) else {
+            return;
+        };
+        if !self.restore_snapshot(world, registry, first) {
+            return;
+        }
+        for frame in first..=last {
+            let inputs = self.simulated[&frame].clone();
+            simulate_frame(world, registry, frame, &inputs);
+            // The events of the first simulation are the ones sent to the rest of the app
+            registry.drain_events(world);
+            let checksum = WorldSnapshot::save(world, registry).checksum(registry);
+            if self.checksums.get(&frame) != Some(&checksum) {
+                error!("Desync detected: frame {frame} simulated differently the second time");
+                world.send_event(DesyncDetected { frame });
+            }
+        }
+    }
+
+    // Forgets the frames that can no longer be rolled back to
+    fn prune(&mut self) {
+        let oldest = match self.mode {
+            RollbackMode::SyncTest { check_distance } => self.frame.saturating_sub(check_distance),
+            // The last confirmed remote input is kept to predict the next ones
+            RollbackMode::PeerToPeer { .. } => {
+                self.remote_frame.min(self.peer_frame).saturating_sub(1)
+            }
+        };
+        self.snapshots.retain(|(frame, _)| *frame >= oldest);
+        self.confirmed.retain(|frame, _| *frame >= oldest);
+        self.simulated.retain(|frame, _| *frame >= oldest);
+        self.checksums.retain(|frame, _| *frame >= oldest);
+    }
+}
+
+fn simulate_frame(
+    world: &mut World,
+    registry: &RollbackRegistry,
+    frame: u32,
+    inputs: &[PackedInput],
+) {
+    let mut player_query = world.query::<(Entity, &RollbackPlayer)>();
+    let players: Vec<(Entity, usize)> = player_query
+        .iter(world)
+        .map(|(entity, player)| (entity, player.0))
+        .collect();
+    for (entity, player) in players {
+        let Some(input) = inputs.get(player) else {
+            continue;
+        };
+        let mut entity = world.entity_mut(entity);
+        if let Some(mut movement) = entity.get_mut::<CharacterMovement>() {
+            movement.contribute(InputSource::Network, input.movement());
+        }
+        if input.fire() {
+            entity.insert(CharacterFire);
+        }
+    }
+
+    // Gameplay reads the frame duration from `Time`, so it gets a fixed clock for the frame
+    let real_time = *world.resource::<Time>();
+    let mut time = Time::<()>::default();
+    time.advance_to(FRAME_DURATION * frame);
+    time.advance_by(FRAME_DURATION);
+    world.insert_resource(time);
+    registry.swap_events(world);
+    world.run_schedule(RollbackSchedule);
+    registry.swap_events(world);
+    world.insert_resource(real_time);
+    mark_rollback_entities(world);
+}
+
+fn advance_rollback(world: &mut World) {
+    let registry = world.resource::<RollbackRegistry>().clone();
+    let delta = world.resource::<Time>().delta();
+    world.resource_scope(|world, mut session: Mut<RollbackSession>| {
+        // Read before re-simulating, since gameplay clears the queued inputs
+        session.gather_local_inputs(world);
+        session.receive_peer_inputs();
+        // A confirmed input differs from the one a frame was simulated with, so simulate it again
+        if let Some(frame) = session.received_frame.take() {
+            let mispredicted = (frame..session.frame)
+                .find(|frame| session.simulated.get(frame) != Some(&session.inputs_for(*frame)));
+            if let Some(frame) = mispredicted {
+                session.resimulate_from(world, &registry, frame);
+            }
+        }
+
+        session.accumulator += delta;
+        let mut frames = 0;
+        while session.accumulator >= FRAME_DURATION
+            && frames < MAX_FRAMES_PER_UPDATE
+            && session.can_advance()
+        {
+            session.accumulator -= FRAME_DURATION;
+            frames += 1;
+            session.confirm_local_inputs();
+            session.advance_frame(world, &registry);
+            session.check_sync(world, &registry);
+        }
+        // Do not build up frames while waiting on the peer
+        session.accumulator = session
+            .accumulator
+            .min(FRAME_DURATION * MAX_FRAMES_PER_UPDATE);
+
+        session.send_confirmed_events(world, &registry);
+        session.send_peer_inputs();
+        session.prune();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restored_projectiles_keep_their_restored_owner() {
+        let mut app = App::new();
+        app.add_plugins(RollbackPlugin);
+        let registry = app.world.resource::<RollbackRegistry>().clone();
+        let world = &mut app.world;
+
+        let owner = world.spawn((Character::bundle(), Rollback)).id();
+        let target = world.spawn((Character::bundle(), Rollback)).id();
+        let projectile = world
+            .spawn((Projectile::bundle(owner, Vec3::ZERO, Vec2::X), Rollback))
+            .id();
+        let snapshot = WorldSnapshot::save(world, &registry);
+
+        world.entity_mut(owner).despawn_recursive();
+        world.entity_mut(projectile).despawn_recursive();
+        // Takes the freed ids, so the restored entities can not get their old ones back
+        for _ in 0..2 {
+            world.spawn_empty();
+        }
+        snapshot.restore(world, &registry);
+
+        let mut character_query = world.query_filtered::<Entity, With<Character>>();
+        let characters: Vec<Entity> = character_query.iter(world).collect();
+        assert_eq!(characters.len(), 2);
+        let restored_owner = *characters
+            .iter()
+            .find(|character| **character != target)
+            .unwrap();
+        assert_ne!(restored_owner, owner);
+
+        let mut projectile_query = world.query::<&Projectile>();
+        let restored = projectile_query.single(world);
+        assert_eq!(restored.owner, restored_owner);
+    }
+}
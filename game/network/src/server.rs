@@ -0,0 +1,246 @@
+use std::{collections::HashMap, io, net::SocketAddr, time::Duration};
+
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    blueprints::{Character, CharacterFire, CharacterMovement, Health, InputSource},
+    transport::UdpTransport,
+    CharacterState, ClientId, ClientMessage, NetworkId, NetworkSet, NetworkSystem, ServerMessage,
+};
+
+// Clients that have not sent anything for this long are disconnected
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+// How often the server sends the state of every character to its clients
+const SNAPSHOT_INTERVAL: Duration = Duration::from_millis(50);
+
+pub struct NetworkServerPlugin {
+    pub address: SocketAddr,
+}
+
+/// This plugin listens for clients on a UDP socket and spawns a `Character` for each of them
+/// Client inputs are applied as `InputSource::Network` movement, and snapshots of every character
+/// are sent back, so the app running this plugin is the authority over gameplay
+impl Plugin for NetworkServerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ServerAddress(self.address))
+            .add_systems(Startup, start_server)
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_client_messages,
+                    apply_remote_inputs,
+                    drop_idle_clients,
+                )
+                    .chain()
+                    .in_set(NetworkSet)
+                    .in_set(NetworkSystem::Server)
+                    .run_if(resource_exists::<NetworkServer>()),
+            )
+            .add_systems(
+                PostUpdate,
+                send_snapshots
+                    .in_set(NetworkSet)
+                    .in_set(NetworkSystem::Server)
+                    .run_if(resource_exists::<NetworkServer>()),
+            );
+
+        #[cfg(debug_assertions)]
+        app.register_type::<NetworkId>()
+            .register_type::<ClientId>()
+            .register_type::<RemoteInput>();
+    }
+}
+
+#[derive(Resource)]
+struct ServerAddress(SocketAddr);
+
+struct RemoteClient {
+    id: ClientId,
+    entity: Entity,
+    last_heard: Duration,
+}
+
+#[derive(Resource)]
+pub struct NetworkServer {
+    transport: UdpTransport,
+    clients: HashMap<SocketAddr, RemoteClient>,
+    next_id: u64,
+    snapshot_timer: Timer,
+}
+
+impl NetworkServer {
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    // Includes the port chosen by the system when the server was bound to port 0
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.transport.local_addr()
+    }
+}
+
+// The most recent input received from a client
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Component, Reflect)]
+struct RemoteInput {
+    sequence: u32,
+    movement: Vec2,
+    // Set until the character fires, so a fire input is never lost between frames
+    fire: bool,
+}
+
+fn start_server(
+    mut commands: Commands,
+    address: Res<ServerAddress>,
+    mut exit: EventWriter<AppExit>,
+) {
+    match UdpTransport::bind(address.0) {
+        Ok(transport) => {
+            info!(
+                "Listening for clients on {}",
+                transport.local_addr().unwrap_or(address.0)
+            );
+            commands.insert_resource(NetworkServer {
+                transport,
+                clients: HashMap::default(),
+                next_id: 0,
+                snapshot_timer: Timer::new(SNAPSHOT_INTERVAL, TimerMode::Repeating),
+            });
+        }
+        Err(error) => {
+            error!("Failed to bind server to {}: {error}", address.0);
+            exit.send(AppExit);
+        }
+    }
+}
+
+// Spread the characters out so they do not spawn on top of each other
+fn spawn_position(id: ClientId) -> Vec2 {
+    let slot = (id.0 % 8) as f32;
+    Vec2::from_angle(slot * std::f32::consts::FRAC_PI_4) * 200.
+}
+
+fn receive_client_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut server: ResMut<NetworkServer>,
+    mut input_query: Query<&mut RemoteInput>,
+) {
+    let now = time.elapsed();
+    for (address, message) in server.transport.receive::<ClientMessage>() {
+        if let Some(client) = server.clients.get_mut(&address) {
+            client.last_heard = now;
+        }
+        match message {
+            ClientMessage::Connect => {
+                let id = match server.clients.get(&address) {
+                    // The welcome was lost, so the client asked again
+                    Some(client) => client.id,
+                    None => {
+                        let id = ClientId(server.next_id);
+                        server.next_id += 1;
+                        let entity = commands
+                            .spawn((
+                                Character::bundle().with_translation(spawn_position(id).extend(0.)),
+                                NetworkId(id.0),
+                                id,
+                                RemoteInput::default(),
+                            ))
+                            .id();
+                        info!("Client {:?} connected from {address}", id);
+                        server.clients.insert(
+                            address,
+                            RemoteClient {
+                                id,
+                                entity,
+                                last_heard: now,
+                            },
+                        );
+                        id
+                    }
+                };
+                server
+                    .transport
+                    .send(address, &ServerMessage::Welcome { client: id });
+            }
+            ClientMessage::Input {
+                sequence,
+                movement,
+                fire,
+            } => {
+                let Some(client) = server.clients.get(&address) else {
+                    continue;
+                };
+                // Clients can send any value, and a NaN would reach every client through the snapshots
+                if !movement.is_finite() {
+                    warn!("Dropping input with invalid movement from {address}");
+                    continue;
+                }
+                if let Ok(mut input) = input_query.get_mut(client.entity) {
+                    if sequence > input.sequence {
+                        input.sequence = sequence;
+                        input.movement = movement.clamp_length_max(1.);
+                    }
+                    input.fire |= fire;
+                }
+            }
+            ClientMessage::Disconnect => {
+                if let Some(client) = server.clients.remove(&address) {
+                    info!("Client {:?} disconnected", client.id);
+                    commands.entity(client.entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+fn apply_remote_inputs(
+    mut commands: Commands,
+    mut input_query: Query<(Entity, &mut RemoteInput, &mut CharacterMovement)>,
+) {
+    for (entity, mut input, mut movement) in input_query.iter_mut() {
+        movement.contribute(InputSource::Network, input.movement);
+        if input.fire {
+            input.fire = false;
+            commands.entity(entity).insert(CharacterFire);
+        }
+    }
+}
+
+fn drop_idle_clients(mut commands: Commands, time: Res<Time>, mut server: ResMut<NetworkServer>) {
+    let now = time.elapsed();
+    server.clients.retain(|_, client| {
+        let connected = now.saturating_sub(client.last_heard) < CLIENT_TIMEOUT;
+        if !connected {
+            info!("Client {:?} timed out", client.id);
+            commands.entity(client.entity).despawn_recursive();
+        }
+        connected
+    });
+}
+
+fn send_snapshots(
+    time: Res<Time>,
+    mut server: ResMut<NetworkServer>,
+    character_query: Query<(&NetworkId, &ClientId, &Transform, &Health), With<Character>>,
+) {
+    if !server.snapshot_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let snapshot = ServerMessage::Snapshot {
+        time: time.elapsed_seconds_f64(),
+        characters: character_query
+            .iter()
+            .map(|(id, owner, transform, health)| CharacterState {
+                id: *id,
+                owner: *owner,
+                translation: transform.translation.xy(),
+                health: health.current,
+                max_health: health.max,
+            })
+            .collect(),
+    };
+    for address in server.clients.keys() {
+        server.transport.send(*address, &snapshot);
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+};
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+// The largest payload a UDP datagram can carry
+const MAX_PACKET_SIZE: usize = 65_507;
+
+// A non-blocking UDP socket that sends and receives bincode encoded messages
+pub(crate) struct UdpTransport {
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+}
+
+impl UdpTransport {
+    pub(crate) fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(UdpTransport {
+            socket,
+            buffer: vec![0; MAX_PACKET_SIZE],
+        })
+    }
+
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub(crate) fn send<T: Serialize>(&self, address: SocketAddr, message: &T) {
+        match bincode::serialize(message) {
+            Ok(bytes) => {
+                if let Err(error) = self.socket.send_to(&bytes, address) {
+                    warn!("Failed to send packet to {address}: {error}");
+                }
+            }
+            Err(error) => error!("Failed to encode packet: {error}"),
+        }
+    }
+
+    // Drains every packet that arrived since the last call
+    pub(crate) fn receive<T: DeserializeOwned>(&mut self) -> Vec<(SocketAddr, T)> {
+        let mut messages = Vec::new();
+        loop {
+            match self.socket.recv_from(&mut self.buffer) {
+                Ok((length, address)) => match bincode::deserialize(&self.buffer[..length]) {
+                    Ok(message) => messages.push((address, message)),
+                    Err(error) => warn!("Dropping malformed packet from {address}: {error}"),
+                },
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                // e.g. the other side is not listening (yet); try again next frame
+                Err(error) => {
+                    debug!("Failed to receive packet: {error}");
+                    break;
+                }
+            }
+        }
+        messages
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+
+use bevy_game_gameplay::GameplayPlugin;
+use bevy_game_network::{
+    blueprints::{Character, CharacterMovement, GameConfig, InputSource},
+    ClientConnection, ClientId, LocalInput, NetworkClientPlugin, NetworkServer,
+    NetworkServerPlugin, NetworkSet,
+};
+
+// Each step sleeps about a frame, so time passes for the gameplay and the sockets
+const FRAME: Duration = Duration::from_millis(16);
+const TIMEOUT: Duration = Duration::from_secs(10);
+// How far a character has to move on the other client to count as moved by its input
+const MOVED_DISTANCE: f32 = 50.;
+// How long a client sends invalid inputs for, long enough for several snapshots to be sent
+const INVALID_INPUT_FRAMES: u32 = 30;
+
+// The movement a client holds, like a key held down
+#[derive(Default)]
+#[derive(Resource)]
+struct HeldMovement(Vec2);
+
+fn hold_movement(
+    held: Res<HeldMovement>,
+    mut movement_query: Query<&mut CharacterMovement, With<LocalInput>>,
+) {
+    for mut movement in movement_query.iter_mut() {
+        movement.contribute(InputSource::Local, held.0);
+    }
+}
+
+fn server_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<GameConfig>()
+        .add_plugins((
+            GameplayPlugin,
+            NetworkServerPlugin {
+                address: SocketAddr::from(([127, 0, 0, 1], 0)),
+            },
+        ));
+    app
+}
+
+fn client_app(server: SocketAddr) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, NetworkClientPlugin))
+        .init_resource::<HeldMovement>()
+        .insert_resource(ClientConnection::connect(server).expect("Failed to bind the client"))
+        .add_systems(Update, hold_movement.before(NetworkSet));
+    app.world.spawn((CharacterMovement::default(), LocalInput));
+    app
+}
+
+fn client_id(client: &App) -> Option<ClientId> {
+    client.world.resource::<ClientConnection>().client()
+}
+
+// The position of every character replicated to the client, by the client controlling it
+fn replicated_characters(client: &mut App) -> HashMap<ClientId, Vec2> {
+    let mut character_query = client
+        .world
+        .query_filtered::<(&ClientId, &Transform), With<Character>>();
+    character_query
+        .iter(&client.world)
+        .map(|(owner, transform)| (*owner, transform.translation.xy()))
+        .collect()
+}
+
+// Updates every app until `done` returns true, panicking with `waiting_for` after the timeout
+fn step_until(
+    apps: &mut [&mut App],
+    waiting_for: &str,
+    mut done: impl FnMut(&mut [&mut App]) -> bool,
+) {
+    let start = Instant::now();
+    loop {
+        for app in apps.iter_mut() {
+            app.update();
+        }
+        if done(apps) {
+            return;
+        }
+        assert!(
+            start.elapsed() < TIMEOUT,
+            "Timed out waiting for {waiting_for}"
+        );
+        thread::sleep(FRAME);
+    }
+}
+
+// A server with two welcomed clients that both see each other's characters,
+// returning the apps and the ids of the clients
+fn connect_two_clients() -> ([App; 3], ClientId, ClientId) {
+    let mut server = server_app();
+    // Binds the socket in `Startup`
+    server.update();
+    let address = server
+        .world
+        .resource::<NetworkServer>()
+        .local_addr()
+        .expect("The server has an address");
+
+    let mut first = client_app(address);
+    let mut second = client_app(address);
+    step_until(
+        &mut [&mut server, &mut first, &mut second],
+        "both clients to be welcomed",
+        |apps| client_id(apps[1]).is_some() && client_id(apps[2]).is_some(),
+    );
+    let first_id = client_id(&first).unwrap();
+    let second_id = client_id(&second).unwrap();
+    assert_ne!(first_id, second_id);
+    assert_eq!(server.world.resource::<NetworkServer>().client_count(), 2);
+
+    step_until(
+        &mut [&mut server, &mut first, &mut second],
+        "both characters to be replicated to both clients",
+        |apps| {
+            apps[1..].iter_mut().all(|client| {
+                let characters = replicated_characters(client);
+                characters.contains_key(&first_id) && characters.contains_key(&second_id)
+            })
+        },
+    );
+    ([server, first, second], first_id, second_id)
+}
+
+#[test]
+fn server_and_two_clients_on_localhost() {
+    let ([mut server, mut moving, mut watching], moving_id, _) = connect_two_clients();
+    let start = replicated_characters(&mut watching)[&moving_id];
+
+    moving.world.resource_mut::<HeldMovement>().0 = Vec2::X;
+    step_until(
+        &mut [&mut server, &mut moving, &mut watching],
+        "the input of one client to move its character on the other client",
+        |apps| {
+            let position = replicated_characters(apps[2])[&moving_id];
+            position.x - start.x > MOVED_DISTANCE
+        },
+    );
+}
+
+#[test]
+fn invalid_inputs_do_not_reach_snapshots() {
+    let ([mut server, mut cheating, mut watching], cheating_id, _) = connect_two_clients();
+    let start = replicated_characters(&mut watching)[&cheating_id];
+
+    cheating.world.resource_mut::<HeldMovement>().0 = Vec2::NAN;
+    for _ in 0..INVALID_INPUT_FRAMES {
+        for app in [&mut server, &mut cheating, &mut watching] {
+            app.update();
+        }
+        for client in [&mut cheating, &mut watching] {
+            let characters = replicated_characters(client);
+            assert!(
+                characters.values().all(|position| position.is_finite()),
+                "A snapshot has a character at {characters:?}"
+            );
+        }
+        thread::sleep(FRAME);
+    }
+
+    // The client can still move its character once it sends valid inputs again
+    cheating.world.resource_mut::<HeldMovement>().0 = Vec2::X;
+    step_until(
+        &mut [&mut server, &mut cheating, &mut watching],
+        "a valid input after the invalid ones to move the character",
+        |apps| {
+            let position = replicated_characters(apps[2])[&cheating_id];
+            position.x - start.x > MOVED_DISTANCE
+        },
+    );
+}
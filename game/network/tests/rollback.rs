@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::ManualEventReader, prelude::*, time::TimeUpdateStrategy};
+
+use bevy_game_gameplay::GameplayPlugin;
+use bevy_game_network::{
+    blueprints::{
+        Character, CharacterFire, CharacterMovement, GameConfig, InputSource, Pickup,
+        PickupCollected, RollbackPlayer,
+    },
+    DesyncDetected, PackedInput, RollbackPlugin, RollbackSession, ROLLBACK_FPS,
+};
+
+const PLAYERS: usize = 2;
+const CHECK_DISTANCE: u32 = 7;
+const FRAMES: u32 = 300;
+
+// The input of a player on a frame: circling at different speeds, and firing on different frames
+fn scripted_input(player: usize, frame: u32) -> PackedInput {
+    let angle = frame as f32 * 0.05 * (player + 1) as f32;
+    let fire = frame % 40 == player as u32 * 13;
+    PackedInput::pack(Vec2::from_angle(angle), fire)
+}
+
+fn apply_scripted_inputs(
+    mut commands: Commands,
+    session: Res<RollbackSession>,
+    mut player_query: Query<(Entity, &RollbackPlayer, &mut CharacterMovement)>,
+) {
+    for (entity, player, mut movement) in player_query.iter_mut() {
+        let input = scripted_input(player.0, session.frame());
+        movement.contribute(InputSource::Local, input.movement());
+        if input.fire() {
+            commands.entity(entity).insert(CharacterFire);
+        }
+    }
+}
+
+// Every update simulates exactly one rollback frame
+fn sync_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_nanos(
+            1_000_000_000 / ROLLBACK_FPS as u64,
+        )))
+        .init_resource::<GameConfig>()
+        .add_plugins((GameplayPlugin, RollbackPlugin))
+        .insert_resource(RollbackSession::sync_test(PLAYERS, CHECK_DISTANCE))
+        .add_systems(PreUpdate, apply_scripted_inputs);
+    app
+}
+
+fn spawn_player(app: &mut App, player: usize, position: Vec2) {
+    app.world.spawn((
+        Character::bundle().with_translation(position.extend(0.)),
+        RollbackPlayer(player),
+    ));
+}
+
+// Updates the app for `frames` frames, counting the events of type `E` the rest of the app reads
+fn count_events<E: Event>(app: &mut App, frames: u32) -> usize {
+    let mut reader = ManualEventReader::<E>::default();
+    let mut count = 0;
+    for _ in 0..frames {
+        app.update();
+        count += reader.read(app.world.resource::<Events<E>>()).count();
+    }
+    count
+}
+
+#[test]
+fn sync_test_simulates_deterministically() {
+    let mut app = sync_test_app();
+    spawn_player(&mut app, 0, Vec2::new(-150., 0.));
+    spawn_player(&mut app, 1, Vec2::new(150., 0.));
+    for x in [-300., -100., 100., 300.] {
+        app.world.spawn(Pickup::coin(Vec2::new(x, 150.)));
+    }
+    app.world
+        .spawn(Pickup::heal(Vec2::new(0., -150.)).respawning_after(Duration::from_millis(500)));
+
+    let desyncs = count_events::<DesyncDetected>(&mut app, FRAMES);
+    assert!(app.world.resource::<RollbackSession>().frame() >= FRAMES - 1);
+    assert_eq!(desyncs, 0, "Re-simulated frames ended in a different state");
+}
+
+#[test]
+fn resimulated_events_are_sent_once() {
+    let mut app = sync_test_app();
+    spawn_player(&mut app, 0, Vec2::ZERO);
+    spawn_player(&mut app, 1, Vec2::new(1000., 0.));
+    // Collected on the first frame, and simulated again `CHECK_DISTANCE` times
+    app.world.spawn(Pickup::coin(Vec2::ZERO));
+
+    let collected = count_events::<PickupCollected>(&mut app, CHECK_DISTANCE * 3);
+    assert_eq!(collected, 1);
+}
@@ -0,0 +1,376 @@
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use bevy_game_controls::Controller;
+use bevy_game_gameplay::{
+    blueprints::{CharacterMovement, Dead, GameConfig, PickupCollected, RonAssetLoader, Velocity},
+    GameplaySet,
+};
+
+use crate::{
+    storage,
+    ui::{format_time, text_style, ButtonColors, TEXT_COLOR},
+    GameState,
+};
+
+const PROGRESS_FILE: &str = "progress.ron";
+const TOAST_DURATION: f32 = 3.;
+
+pub struct AchievementsPlugin;
+
+/// This plugin counts lifetime statistics of local players while in `GameState::Playing`
+/// Achievements from `assets/config/game.achievements.ron` unlock once a statistic reaches their goal,
+/// which shows a toast, and the achievements page is drawn during `GameState::Achievements`
+/// Statistics and unlocked achievements are saved whenever a level is left or an achievement unlocks
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<AchievementList>()
+            .register_asset_loader(RonAssetLoader::<AchievementList>::new(&[
+                "achievements.ron",
+            ]))
+            .add_event::<AchievementUnlocked>()
+            .insert_resource(storage::load::<Progress>(PROGRESS_FILE).unwrap_or_default())
+            .add_systems(Startup, load_achievements)
+            .add_systems(
+                Update,
+                (
+                    (
+                        count_distance,
+                        count_pickups.after(GameplaySet),
+                        count_deaths,
+                        count_play_time,
+                    )
+                        .run_if(in_state(GameState::Playing)),
+                    unlock_achievements,
+                    spawn_toasts,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, expire_toasts)
+            .add_systems(OnExit(GameState::Playing), save_progress)
+            .add_systems(OnEnter(GameState::Achievements), setup_achievements_page)
+            .add_systems(
+                Update,
+                handle_back_button_click.run_if(in_state(GameState::Achievements)),
+            )
+            .add_systems(OnExit(GameState::Achievements), cleanup_achievements_page);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Stat {
+    Distance,
+    Pickups,
+    Deaths,
+    // In seconds
+    PlayTime,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AchievementDefinition {
+    // Identifies the achievement in the save file, so names and descriptions may change
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub stat: Stat,
+    pub goal: f32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Asset, TypePath)]
+pub struct AchievementList {
+    pub achievements: Vec<AchievementDefinition>,
+}
+
+// Counted across every session for the players on this device
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub distance: f32,
+    pub pickups: u32,
+    pub deaths: u32,
+    pub play_time: f32,
+}
+
+impl LifetimeStats {
+    pub fn get(&self, stat: Stat) -> f32 {
+        match stat {
+            Stat::Distance => self.distance,
+            Stat::Pickups => self.pickups as f32,
+            Stat::Deaths => self.deaths as f32,
+            Stat::PlayTime => self.play_time,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Resource)]
+#[serde(default)]
+pub struct Progress {
+    pub stats: LifetimeStats,
+    // The ids of unlocked achievements
+    pub unlocked: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug)]
+#[derive(Event)]
+pub struct AchievementUnlocked {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Resource)]
+struct AchievementListHandle(Handle<AchievementList>);
+
+fn load_achievements(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AchievementListHandle(
+        asset_server.load("config/game.achievements.ron"),
+    ));
+}
+
+fn count_distance(
+    time: Res<Time>,
+    mut progress: ResMut<Progress>,
+    player_query: Query<&Velocity, (With<CharacterMovement>, With<Controller>, Without<Dead>)>,
+) {
+    let distance: f32 = player_query
+        .iter()
+        .map(|velocity| velocity.0.length() * time.delta_seconds())
+        .sum();
+    if distance > 0. {
+        progress.stats.distance += distance;
+    }
+}
+
+// Rollback sessions only send `PickupCollected` once the frame is confirmed, so pickups
+// are not counted again when frames are simulated again
+// Counted in the frame they are collected in, so the last pickup of a level is not missed
+fn count_pickups(
+    mut progress: ResMut<Progress>,
+    mut pickup_events: EventReader<PickupCollected>,
+    player_query: Query<(), With<Controller>>,
+) {
+    for event in pickup_events.read() {
+        if player_query.contains(event.character) {
+            progress.stats.pickups += 1;
+        }
+    }
+}
+
+fn count_deaths(
+    mut progress: ResMut<Progress>,
+    player_query: Query<(), (With<Controller>, Added<Dead>)>,
+) {
+    let deaths = player_query.iter().count() as u32;
+    if deaths > 0 {
+        progress.stats.deaths += deaths;
+    }
+}
+
+fn count_play_time(time: Res<Time>, mut progress: ResMut<Progress>) {
+    progress.stats.play_time += time.delta_seconds();
+}
+
+fn unlock_achievements(
+    mut progress: ResMut<Progress>,
+    handle: Option<Res<AchievementListHandle>>,
+    lists: Res<Assets<AchievementList>>,
+    mut unlocked_events: EventWriter<AchievementUnlocked>,
+) {
+    let Some(list) = handle.and_then(|handle| lists.get(&handle.0)) else {
+        return;
+    };
+    let mut unlocked_any = false;
+    for achievement in list.achievements.iter() {
+        if progress.unlocked.contains(&achievement.id)
+            || progress.stats.get(achievement.stat) < achievement.goal
+        {
+            continue;
+        }
+        info!("Unlocked achievement \"{}\"", achievement.name);
+        progress.unlocked.insert(achievement.id.clone());
+        unlocked_events.send(AchievementUnlocked {
+            name: achievement.name.clone(),
+            description: achievement.description.clone(),
+        });
+        unlocked_any = true;
+    }
+    if unlocked_any {
+        storage::save(PROGRESS_FILE, &*progress);
+    }
+}
+
+fn save_progress(progress: Res<Progress>) {
+    storage::save(PROGRESS_FILE, &*progress);
+}
+
+#[derive(Component)]
+struct Toast(Timer);
+
+fn spawn_toasts(mut commands: Commands, mut unlocked_events: EventReader<AchievementUnlocked>) {
+    for (index, event) in unlocked_events.read().enumerate() {
+        commands
+            .spawn((
+                Name::new("Achievement Toast"),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        right: Val::Px(10.),
+                        top: Val::Px(10. + 70. * index as f32),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(10.)),
+                        ..default()
+                    },
+                    background_color: Color::rgba(0.1, 0.1, 0.1, 0.9).into(),
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                },
+                Toast(Timer::from_seconds(TOAST_DURATION, TimerMode::Once)),
+            ))
+            .with_children(|children| {
+                children.spawn(TextBundle::from_section(
+                    format!("Achievement unlocked: {}", event.name),
+                    text_style(20.0),
+                ));
+                children.spawn(TextBundle::from_section(
+                    event.description.clone(),
+                    text_style(15.0),
+                ));
+            });
+    }
+}
+
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in toast_query.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[derive(Component)]
+struct AchievementsPage;
+
+#[derive(Component)]
+struct BackButton;
+
+fn setup_achievements_page(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    progress: Res<Progress>,
+    handle: Res<AchievementListHandle>,
+    lists: Res<Assets<AchievementList>>,
+) {
+    let stats = progress.stats;
+    let achievements = lists
+        .get(&handle.0)
+        .map(|list| list.achievements.as_slice())
+        .unwrap_or_default();
+
+    commands
+        .spawn((
+            Name::new("Achievements"),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            },
+            AchievementsPage,
+        ))
+        .with_children(|children| {
+            children.spawn(TextBundle::from_section("Achievements", text_style(60.0)));
+            children.spawn(TextBundle::from_section(
+                format!(
+                    "Distance: {:.0}   Pickups: {}   Deaths: {}   Play time: {}",
+                    stats.distance,
+                    stats.pickups,
+                    stats.deaths,
+                    format_time(stats.play_time),
+                ),
+                text_style(20.0),
+            ));
+
+            for achievement in achievements {
+                let unlocked = progress.unlocked.contains(&achievement.id);
+                let status = if unlocked {
+                    "Unlocked".to_string()
+                } else {
+                    format!(
+                        "{:.0} / {:.0}",
+                        stats.get(achievement.stat).min(achievement.goal),
+                        achievement.goal
+                    )
+                };
+                let color = if unlocked {
+                    TEXT_COLOR
+                } else {
+                    Color::rgb(0.5, 0.5, 0.5)
+                };
+                children.spawn(TextBundle::from_section(
+                    format!(
+                        "{} - {} ({status})",
+                        achievement.name, achievement.description
+                    ),
+                    TextStyle {
+                        color,
+                        ..text_style(20.0)
+                    },
+                ));
+            }
+
+            let button_colors = ButtonColors::from(&config.ui.button);
+            children
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(140.0),
+                            height: Val::Px(50.0),
+                            margin: UiRect::top(Val::Px(20.)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        background_color: button_colors.normal.into(),
+                        ..Default::default()
+                    },
+                    button_colors,
+                    BackButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Back", text_style(40.0)));
+                });
+        });
+}
+
+fn handle_back_button_click(
+    mut next_state: ResMut<NextState<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<BackButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if let Interaction::Pressed = *interaction {
+            next_state.set(GameState::Menu);
+        }
+    }
+}
+
+fn cleanup_achievements_page(
+    mut commands: Commands,
+    page_query: Query<Entity, With<AchievementsPage>>,
+) {
+    for entity in page_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    diagnostic::{DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+use bevy_game_controls::{ActionState, CharacterControl, Controller};
+use bevy_game_flavor::loading::AssetLoadState;
+
+use crate::{menu::MenuLoadState, ui::text_style, GameState};
+
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+// The number of frames drawn in the frame time graph
+const GRAPH_FRAMES: usize = 120;
+// Frame times at or above this many milliseconds fill the whole graph height
+const GRAPH_MAX_MS: f32 = 50.;
+const GRAPH_HEIGHT: f32 = 60.;
+
+pub struct DebugOverlayPlugin;
+
+/// This plugin draws diagnostics over the game while toggled with F3
+/// It shows FPS, a frame time graph, the entity count, the current states
+/// and the action state of every player's controller
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        // The editor adds these as well
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+            app.add_plugins(EntityCountDiagnosticsPlugin);
+        }
+        app.init_resource::<FrameTimes>()
+            .add_systems(Startup, spawn_debug_overlay)
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    record_frame_time,
+                    (update_debug_text, update_frame_time_graph).run_if(debug_overlay_visible),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Default)]
+#[derive(Resource)]
+struct FrameTimes(VecDeque<f32>);
+
+#[derive(Component)]
+struct DebugOverlay;
+
+#[derive(Component)]
+struct DebugText;
+
+// One bar of the frame time graph, counting back from the latest frame
+#[derive(Component)]
+struct FrameTimeBar(usize);
+
+fn spawn_debug_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Debug Overlay"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(10.),
+                    top: Val::Px(10.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(5.),
+                    padding: UiRect::all(Val::Px(5.)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            DebugOverlay,
+        ))
+        .with_children(|children| {
+            children.spawn((TextBundle::from_section("", text_style(15.0)), DebugText));
+            children
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::RowReverse,
+                        align_items: AlignItems::FlexEnd,
+                        height: Val::Px(GRAPH_HEIGHT),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|children| {
+                    for index in 0..GRAPH_FRAMES {
+                        children.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    width: Val::Px(2.),
+                                    height: Val::Px(0.),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            FrameTimeBar(index),
+                        ));
+                    }
+                });
+        });
+}
+
+fn debug_overlay_visible(overlay_query: Query<&Visibility, With<DebugOverlay>>) -> bool {
+    overlay_query
+        .iter()
+        .any(|visibility| *visibility == Visibility::Inherited)
+}
+
+fn toggle_debug_overlay(
+    keys: Res<Input<KeyCode>>,
+    mut overlay_query: Query<&mut Visibility, With<DebugOverlay>>,
+) {
+    if !keys.just_pressed(TOGGLE_KEY) {
+        return;
+    }
+    for mut visibility in overlay_query.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+fn record_frame_time(time: Res<Time<Real>>, mut frame_times: ResMut<FrameTimes>) {
+    frame_times.0.push_front(time.delta_seconds() * 1000.);
+    frame_times.0.truncate(GRAPH_FRAMES);
+}
+
+fn update_debug_text(
+    diagnostics: Res<DiagnosticsStore>,
+    game_state: Res<State<GameState>>,
+    asset_load_state: Res<State<AssetLoadState>>,
+    menu_load_state: Res<State<MenuLoadState>>,
+    player_query: Query<(&Controller, &ActionState<CharacterControl>)>,
+    mut text_query: Query<&mut Text, With<DebugText>>,
+) {
+    let diagnostic = |id| {
+        diagnostics
+            .get(id)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or_default()
+    };
+    let mut lines = vec![
+        format!(
+            "FPS: {:.0} ({:.2} ms)",
+            diagnostic(FrameTimeDiagnosticsPlugin::FPS),
+            diagnostic(FrameTimeDiagnosticsPlugin::FRAME_TIME),
+        ),
+        format!(
+            "Entities: {:.0}",
+            diagnostic(EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+        ),
+        format!("GameState: {:?}", game_state.get()),
+        format!("AssetLoadState: {:?}", asset_load_state.get()),
+        format!("MenuLoadState: {:?}", menu_load_state.get()),
+    ];
+    for (controller, action_state) in player_query.iter() {
+        let movement = action_state
+            .axis_pair(CharacterControl::Move)
+            .map(|axis| axis.xy())
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}: Move ({:.2}, {:.2}) Pressed {:?}",
+            controller.label(),
+            movement.x,
+            movement.y,
+            action_state.get_pressed(),
+        ));
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn update_frame_time_graph(
+    frame_times: Res<FrameTimes>,
+    mut bar_query: Query<(&FrameTimeBar, &mut Style, &mut BackgroundColor)>,
+) {
+    for (bar, mut style, mut color) in bar_query.iter_mut() {
+        let frame_time = frame_times.0.get(bar.0).copied().unwrap_or_default();
+        style.height = Val::Px((frame_time / GRAPH_MAX_MS).min(1.) * GRAPH_HEIGHT);
+        *color = if frame_time > 1000. / 30. {
+            Color::RED
+        } else if frame_time > 1000. / 60. {
+            Color::YELLOW
+        } else {
+            Color::GREEN
+        }
+        .into();
+    }
+}
@@ -0,0 +1,197 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use bevy_game_controls::Controller;
+use bevy_game_gameplay::{
+    blueprints::{Character, Health, Score},
+    LevelTimer,
+};
+
+use crate::{
+    ui::{format_time, text_style},
+    GameState,
+};
+
+pub struct HudPlugin;
+
+/// This plugin draws the in-game HUD with a panel per player and the level timer
+/// The HUD is only drawn during the State `GameState::Playing` and is removed when that state is exited
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup_hud)
+            .add_systems(
+                Update,
+                (
+                    spawn_player_panels,
+                    despawn_player_panels,
+                    update_player_panels,
+                    update_level_timer.run_if(resource_exists::<LevelTimer>()),
+                    scale_hud_text,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup_hud);
+    }
+}
+
+// Font sizes are authored for a window of this height and scaled from there
+const REFERENCE_HEIGHT: f32 = 720.;
+
+#[derive(Component)]
+struct Hud;
+
+#[derive(Component)]
+struct PlayerPanels;
+
+#[derive(Component)]
+struct PlayerPanel(Entity);
+
+#[derive(Component)]
+struct LevelTimerText;
+
+#[derive(Component)]
+struct HudText {
+    font_size: f32,
+}
+
+fn setup_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Hud"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.),
+                    width: Val::Percent(100.),
+                    flex_direction: FlexDirection::Row,
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::FlexStart,
+                    padding: UiRect::all(Val::Vh(1.5)),
+                    ..default()
+                },
+                ..default()
+            },
+            Hud,
+        ))
+        .with_children(|children| {
+            children.spawn((
+                Name::new("Player Panels"),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Vw(1.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+                PlayerPanels,
+            ));
+            children.spawn((
+                Name::new("Level Timer"),
+                TextBundle::from_section("00:00.0", text_style(32.)),
+                HudText { font_size: 32. },
+                LevelTimerText,
+            ));
+        });
+}
+
+fn spawn_player_panels(
+    mut commands: Commands,
+    character_query: Query<(Entity, &Controller), (With<Character>, Added<Controller>)>,
+    container_query: Query<Entity, With<PlayerPanels>>,
+) {
+    let Ok(container) = container_query.get_single() else {
+        return;
+    };
+    for (character, controller) in character_query.iter() {
+        commands.entity(container).with_children(|children| {
+            children
+                .spawn((
+                    Name::new(format!("{} Panel", controller.label())),
+                    NodeBundle {
+                        style: Style {
+                            min_width: Val::Vw(15.),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::all(Val::Vh(1.)),
+                            ..default()
+                        },
+                        background_color: Color::rgba(0.15, 0.15, 0.15, 0.8).into(),
+                        ..default()
+                    },
+                    PlayerPanel(character),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        TextBundle::from_sections([
+                            TextSection::new(controller.label(), text_style(20.)),
+                            TextSection::new("", text_style(20.)),
+                            TextSection::new("", text_style(20.)),
+                        ]),
+                        HudText { font_size: 20. },
+                    ));
+                });
+        });
+    }
+}
+
+fn despawn_player_panels(
+    mut commands: Commands,
+    panel_query: Query<(Entity, &PlayerPanel)>,
+    character_query: Query<(), With<Character>>,
+) {
+    for (panel, player) in panel_query.iter() {
+        if !character_query.contains(player.0) {
+            commands.entity(panel).despawn_recursive();
+        }
+    }
+}
+
+fn update_player_panels(
+    panel_query: Query<(&PlayerPanel, &Children)>,
+    character_query: Query<(&Score, &Health)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (player, children) in panel_query.iter() {
+        let Ok((score, health)) = character_query.get(player.0) else {
+            continue;
+        };
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[1].value = format!("\nScore: {}", score.0);
+            text.sections[2].value = format!("\nHealth: {:.0}/{:.0}", health.current, health.max);
+        }
+    }
+}
+
+fn update_level_timer(
+    timer: Res<LevelTimer>,
+    mut text_query: Query<&mut Text, With<LevelTimerText>>,
+) {
+    let elapsed = timer.0.elapsed_secs();
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format_time(elapsed);
+    }
+}
+
+fn scale_hud_text(
+    window_query: Query<Ref<Window>, With<PrimaryWindow>>,
+    mut text_query: Query<(Ref<HudText>, &mut Text)>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let scale = window.height() / REFERENCE_HEIGHT;
+    for (hud_text, mut text) in text_query.iter_mut() {
+        if window.is_changed() || hud_text.is_added() {
+            for section in text.sections.iter_mut() {
+                section.style.font_size = hud_text.font_size * scale;
+            }
+        }
+    }
+}
+
+fn cleanup_hud(mut commands: Commands, hud: Query<Entity, With<Hud>>) {
+    for entity in hud.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
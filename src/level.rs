@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use bevy_game_controls::Controller;
+use bevy_game_gameplay::{
+    blueprints::{Character, ExitZone, Pickup, Projectile, Score, Terrain, Wall},
+    LevelObjective, LevelOutcome, LevelStats, LevelTimer,
+};
+
+use crate::{rollback::rollback_players, scene::SceneLevel, GameState, RollbackSettings};
+
+pub struct LevelPlugin;
+
+/// This plugin spawns the current level when entering `GameState::Playing` and despawns it on exit
+/// Once the level objective is met or every player died, the results of the run are recorded
+/// and the game moves on to `GameState::LevelComplete` or `GameState::GameOver`
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Levels>()
+            .init_resource::<CurrentLevel>()
+            .add_systems(
+                Startup,
+                add_scene_level.run_if(resource_exists::<SceneLevel>()),
+            )
+            .add_systems(OnEnter(GameState::Playing), spawn_level)
+            .add_systems(Update, finish_level.run_if(in_state(GameState::Playing)))
+            .add_systems(OnExit(GameState::Playing), cleanup_level);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LevelDefinition {
+    pub name: &'static str,
+    pub objective: LevelObjective,
+    pub players: Vec<(Controller, Vec2)>,
+    pub coins: Vec<Vec2>,
+    // Heal pickups respawn a while after being collected
+    pub heals: Vec<Vec2>,
+    pub exit: Option<Vec2>,
+    // Time-trial levels keep the fastest run as a high score and replay it as a ghost
+    pub time_trial: bool,
+    // A scene file, such as one saved from the editor, spawned in addition to the entities above
+    // Its `LevelObjective` replaces the objective above once the scene is spawned
+    pub scene: Option<String>,
+}
+
+#[derive(Debug)]
+#[derive(Resource)]
+pub struct Levels(pub Vec<LevelDefinition>);
+
+impl Default for Levels {
+    fn default() -> Self {
+        Levels(vec![
+            LevelDefinition {
+                name: "Collect the coins",
+                objective: LevelObjective::CollectItems(4),
+                players: vec![(Controller::One, Vec2::ZERO)],
+                coins: vec![
+                    Vec2::new(-300., 150.),
+                    Vec2::new(-200., 150.),
+                    Vec2::new(200., 150.),
+                    Vec2::new(300., 150.),
+                ],
+                heals: vec![Vec2::new(0., -200.)],
+                exit: None,
+                time_trial: true,
+                scene: None,
+            },
+            LevelDefinition {
+                name: "Find the exit",
+                objective: LevelObjective::ReachExit,
+                players: vec![(Controller::One, Vec2::new(-400., 0.))],
+                coins: vec![Vec2::new(-100., 200.), Vec2::new(100., -200.)],
+                heals: vec![],
+                exit: Some(Vec2::new(400., 0.)),
+                time_trial: true,
+                scene: None,
+            },
+            LevelDefinition {
+                name: "Survive",
+                objective: LevelObjective::Survive(Duration::from_secs(30)),
+                players: vec![
+                    (Controller::One, Vec2::new(-200., 0.)),
+                    (Controller::Two, Vec2::new(200., 0.)),
+                ],
+                coins: vec![],
+                heals: vec![Vec2::new(0., 200.), Vec2::new(0., -200.)],
+                exit: None,
+                time_trial: false,
+                scene: None,
+            },
+        ])
+    }
+}
+
+impl Levels {
+    pub fn get(&self, index: usize) -> Option<&LevelDefinition> {
+        self.0.get(index)
+    }
+
+    pub fn has_next(&self, current: &CurrentLevel) -> bool {
+        current.0 + 1 < self.0.len()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Resource)]
+pub struct CurrentLevel(pub usize);
+
+// A summary of the last finished level, displayed on the results screen
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct LevelResults {
+    pub name: &'static str,
+    pub outcome: LevelOutcome,
+    pub elapsed: Duration,
+    pub stats: LevelStats,
+    pub scores: Vec<(Controller, u32)>,
+}
+
+fn add_scene_level(scene_level: Res<SceneLevel>, mut levels: ResMut<Levels>) {
+    levels.0.insert(
+        0,
+        LevelDefinition {
+            name: "Custom level",
+            objective: LevelObjective::ReachExit,
+            players: vec![],
+            coins: vec![],
+            heals: vec![],
+            exit: None,
+            time_trial: false,
+            scene: Some(scene_level.0.clone()),
+        },
+    );
+}
+
+fn spawn_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut scene_spawner: ResMut<SceneSpawner>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    rollback_settings: Option<Res<RollbackSettings>>,
+) {
+    let Some(level) = levels.get(current_level.0) else {
+        error!("Level {} does not exist", current_level.0);
+        return;
+    };
+    info!("Starting level \"{}\"", level.name);
+
+    let players = match rollback_settings {
+        Some(_) => rollback_players(&level.players),
+        None => level.players.clone(),
+    };
+    for (controller, position) in players.iter() {
+        commands.spawn((
+            Character::bundle().with_translation(position.extend(0.)),
+            *controller,
+        ));
+    }
+    for position in level.coins.iter() {
+        commands.spawn(Pickup::coin(*position));
+    }
+    for position in level.heals.iter() {
+        commands.spawn(Pickup::heal(*position).respawning_after(Duration::from_secs(10)));
+    }
+    if let Some(position) = level.exit {
+        commands.spawn(ExitZone::bundle(position));
+    }
+    if let Some(scene) = &level.scene {
+        scene_spawner.spawn_dynamic(asset_server.load::<DynamicScene>(scene.clone()));
+    }
+
+    commands.insert_resource(level.objective);
+    commands.insert_resource(LevelTimer::default());
+    commands.insert_resource(LevelStats::default());
+}
+
+fn finish_level(
+    mut commands: Commands,
+    mut outcomes: EventReader<LevelOutcome>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    timer: Res<LevelTimer>,
+    stats: Res<LevelStats>,
+    player_query: Query<(&Controller, &Score), With<Character>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(outcome) = outcomes.read().last().copied() else {
+        return;
+    };
+    commands.insert_resource(LevelResults {
+        name: levels
+            .get(current_level.0)
+            .map(|level| level.name)
+            .unwrap_or_default(),
+        outcome,
+        elapsed: timer.0.elapsed(),
+        stats: *stats,
+        scores: player_query
+            .iter()
+            .map(|(controller, score)| (*controller, score.0))
+            .collect(),
+    });
+    next_state.set(match outcome {
+        LevelOutcome::Won => GameState::LevelComplete,
+        LevelOutcome::Lost => GameState::GameOver,
+    });
+}
+
+fn cleanup_level(
+    mut commands: Commands,
+    level_query: Query<
+        Entity,
+        Or<(
+            With<Character>,
+            With<Pickup>,
+            With<Projectile>,
+            With<ExitZone>,
+            With<Terrain>,
+            With<Wall>,
+        )>,
+    >,
+) {
+    for entity in level_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<LevelObjective>();
+    commands.remove_resource::<LevelTimer>();
+    commands.remove_resource::<LevelStats>();
+}
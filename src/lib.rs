@@ -1,4 +1,4 @@
-#![allow(clippy::type_complexity)]
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
 
 use bevy::prelude::*;
 
@@ -6,22 +6,44 @@ pub use bevy_game_controls as controls;
 pub use bevy_game_flavor as flavor;
 pub use bevy_game_gameplay as gameplay;
 pub use bevy_game_gameplay::blueprints;
+pub use bevy_game_network as network;
 
-use bevy_game_controls::{ControllerBundle, ControllerPlugin, ControllerSet};
+use bevy_game_controls::{ControllerPlugin, ControllerSet};
 use bevy_game_flavor::{
     audio::AudioSet, graphics::GraphicsSet, loading::AssetLoadState, FlavorPlugin,
 };
-use bevy_game_gameplay::{blueprints::Character, GameplayPlugin, GameplaySet};
+use bevy_game_gameplay::{
+    blueprints::{BlueprintsPlugin, GameCamera, GameConfigPlugin},
+    GameplayPlugin, GameplaySet,
+};
+use bevy_game_network::NetworkSet;
 
+mod achievements;
+#[cfg(feature = "debug_overlay")]
+mod debug_overlay;
+mod hud;
+mod level;
 mod menu;
-use menu::MenuLoadState;
+pub use menu::MenuLoadState;
+mod online;
+pub use online::ServerAddress;
+mod records;
+mod results;
+mod rollback;
+pub use rollback::RollbackSettings;
+mod scene;
+pub use scene::{
+    load_level_scene, restore_level, save_level_scene, snapshot_level, LevelSceneError, SceneLevel,
+};
+mod storage;
+mod ui;
 
 // This example game uses States to separate logic
 // See https://bevy-cheatbook.github.io/programming/states.html
 // Or https://github.com/bevyengine/bevy/blob/main/examples/ecs/state.rs
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[derive(States)]
-enum GameState {
+pub enum GameState {
     // During the loading State the LoadingPlugin will load our assets
     #[default]
     Loading,
@@ -29,25 +51,67 @@ enum GameState {
     Playing,
     // Here the menu is drawn and waiting for player interaction
     Menu,
+    // The level objective was met and the results are shown
+    LevelComplete,
+    // Every player died and the results are shown
+    GameOver,
+    // Connected to a server that runs the gameplay for every player
+    Online,
+    // The lifetime statistics and achievements are shown
+    Achievements,
+}
+
+impl GameState {
+    pub const ALL: [GameState; 7] = [
+        GameState::Loading,
+        GameState::Playing,
+        GameState::Menu,
+        GameState::LevelComplete,
+        GameState::GameOver,
+        GameState::Online,
+        GameState::Achievements,
+    ];
 }
 
+// The state to enter once loading finishes, instead of `GameState::Menu`
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource)]
+pub struct StartState(pub GameState);
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GameState>()
             .add_plugins((
+                GameConfigPlugin,
+                BlueprintsPlugin,
+                ui::UiPlugin,
+                achievements::AchievementsPlugin,
                 menu::MenuPlugin,
+                hud::HudPlugin,
+                level::LevelPlugin,
+                scene::LevelScenePlugin,
+                results::ResultsPlugin,
+                records::RecordsPlugin,
+                online::OnlinePlugin,
+                rollback::RollbackModePlugin,
                 FlavorPlugin,
                 GameplayPlugin,
                 ControllerPlugin,
             ))
             .configure_sets(
                 Update,
-                (ControllerSet, AudioSet, GraphicsSet, GameplaySet).chain(),
+                (
+                    ControllerSet,
+                    NetworkSet,
+                    AudioSet,
+                    GraphicsSet,
+                    GameplaySet,
+                )
+                    .chain(),
             )
-            // TODO: Spawn scenes dynamically, or make a new plugin
-            .add_systems(OnEnter(GameState::Playing), spawn_character)
+            .add_systems(Startup, spawn_camera)
             .add_systems(
                 Update,
                 detect_menu_ready.run_if(
@@ -55,6 +119,9 @@ impl Plugin for GamePlugin {
                         .or_else(resource_exists_and_changed::<State<AssetLoadState>>()),
                 ),
             );
+
+        #[cfg(feature = "debug_overlay")]
+        app.add_plugins(debug_overlay::DebugOverlayPlugin);
     }
 }
 
@@ -62,17 +129,18 @@ fn detect_menu_ready(
     menu_load_state: Res<State<MenuLoadState>>,
     asset_load_state: Res<State<AssetLoadState>>,
     game_state: Res<State<GameState>>,
+    start_state: Option<Res<StartState>>,
     mut next_game_state: ResMut<NextState<GameState>>,
 ) {
     if let (MenuLoadState::Active, AssetLoadState::Active) =
         (menu_load_state.get(), asset_load_state.get())
     {
         if *game_state.get() == GameState::Loading {
-            next_game_state.set(GameState::Menu);
+            next_game_state.set(start_state.map_or(GameState::Menu, |state| state.0));
         }
     }
 }
 
-fn spawn_character(mut commands: Commands) {
-    commands.spawn((Character::bundle(), ControllerBundle::key_controller_one()));
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((Camera2dBundle::default(), GameCamera));
 }
@@ -2,6 +2,7 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::io::Cursor;
+use std::net::SocketAddr;
 use winit::window::Icon;
 
 use bevy::asset::AssetMetaCheck;
@@ -9,12 +10,50 @@ use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy::winit::WinitWindows;
 use bevy::DefaultPlugins;
+use clap::Parser;
 
-use bevy_game::GamePlugin; // ToDo: Replace bevy_game with your new crate name.
+use bevy_game::{GamePlugin, RollbackSettings, SceneLevel, ServerAddress}; // ToDo: Replace bevy_game with your new crate name.
+
+#[derive(Debug, Parser)]
+pub struct Arguments {
+    /// A server to join from the "Play Online" button of the menu
+    #[arg(long, value_name = "ADDRESS")]
+    pub connect: Option<SocketAddr>,
+    /// A level scene to play as the first level, as an asset path such as `levels/editor.scn.ron`,
+    /// or as a file such as `editor/assets/levels/editor.scn.ron`
+    #[arg(long, value_name = "PATH")]
+    pub level: Option<String>,
+    /// Plays levels in lockstep with another instance, from the local address with the peer address
+    #[arg(long, num_args = 2, value_names = ["LOCAL", "PEER"], conflicts_with = "sync_test")]
+    pub rollback: Option<Vec<SocketAddr>>,
+    /// Checks that levels re-simulate deterministically, re-simulating this many frames every frame
+    #[arg(long, value_name = "FRAMES", num_args = 0..=1, default_missing_value = "7")]
+    pub sync_test: Option<u32>,
+}
+
+impl Arguments {
+    fn rollback_settings(&self) -> Option<RollbackSettings> {
+        if let Some(check_distance) = self.sync_test {
+            return Some(RollbackSettings::SyncTest { check_distance });
+        }
+        match self.rollback.as_deref() {
+            Some(&[local, peer]) => Some(RollbackSettings::PeerToPeer { local, peer }),
+            _ => None,
+        }
+    }
+}
 
 fn main() {
-    App::new()
-        .insert_resource(Msaa::Off)
+    let args = Arguments::parse();
+
+    let mut app = App::new();
+    // A level file is read from an asset source, which has to be registered before `DefaultPlugins`
+    #[cfg(not(target_arch = "wasm32"))]
+    let level = args.level.map(|path| SceneLevel::from_path(&mut app, path));
+    #[cfg(target_arch = "wasm32")]
+    let level = args.level.map(SceneLevel);
+
+    app.insert_resource(Msaa::Off)
         .insert_resource(AssetMetaCheck::Never)
         .insert_resource(ClearColor(Color::rgb(0.4, 0.4, 0.4)))
         .add_plugins(DefaultPlugins.set(WindowPlugin {
@@ -31,8 +70,19 @@ fn main() {
             ..default()
         }))
         .add_plugins(GamePlugin)
-        .add_systems(Startup, set_window_icon)
-        .run();
+        .add_systems(Startup, set_window_icon);
+
+    if let Some(address) = args.connect {
+        app.insert_resource(ServerAddress(address));
+    }
+    if let Some(level) = level {
+        app.insert_resource(level);
+    }
+    if let Some(settings) = args.rollback_settings() {
+        app.insert_resource(settings);
+    }
+
+    app.run();
 }
 
 // Sets the icon on windows and X11
@@ -3,8 +3,12 @@ use bevy_asset_loader::{
     asset_collection::AssetCollection,
     loading_state::{config::ConfigureLoadingState, LoadingState, LoadingStateAppExt},
 };
+use bevy_game_gameplay::blueprints::GameConfig;
 
-use crate::GameState;
+use crate::{
+    ui::{text_style, ButtonColors},
+    GameState, ServerAddress,
+};
 
 #[derive(AssetCollection, Resource)]
 pub struct LogoAssets {
@@ -40,39 +44,22 @@ impl Plugin for MenuPlugin {
             .add_systems(OnEnter(GameState::Menu), setup_menu)
             .add_systems(
                 Update,
-                (
-                    handle_play_button_click,
-                    handle_logo_button_click,
-                    render_button_interactions,
-                )
+                (handle_play_button_click, handle_logo_button_click)
                     .run_if(in_state(GameState::Menu)),
             )
             .add_systems(OnExit(GameState::Menu), cleanup_menu);
     }
 }
 
-#[derive(Component)]
-struct ButtonColors {
-    normal: Color,
-    hovered: Color,
-    active: Color,
-}
-
-impl Default for ButtonColors {
-    fn default() -> Self {
-        ButtonColors {
-            normal: Color::rgb(0.15, 0.15, 0.15),
-            hovered: Color::rgb(0.25, 0.25, 0.25),
-            active: Color::rgb(0.30, 0.30, 0.30),
-        }
-    }
-}
-
 #[derive(Component)]
 struct Menu;
 
-fn setup_menu(mut commands: Commands, textures: Res<LogoAssets>) {
-    commands.spawn(Camera2dBundle::default());
+fn setup_menu(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    textures: Res<LogoAssets>,
+    server_address: Option<Res<ServerAddress>>,
+) {
     commands
         .spawn((
             Name::new("Menu"),
@@ -90,7 +77,7 @@ fn setup_menu(mut commands: Commands, textures: Res<LogoAssets>) {
             Menu,
         ))
         .with_children(|children| {
-            let button_colors = ButtonColors::default();
+            let button_colors = ButtonColors::from(&config.ui.button);
             children
                 .spawn((
                     ButtonBundle {
@@ -108,15 +95,52 @@ fn setup_menu(mut commands: Commands, textures: Res<LogoAssets>) {
                     ChangeState(GameState::Playing),
                 ))
                 .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Play",
-                        TextStyle {
-                            font_size: 40.0,
-                            color: Color::rgb(0.9, 0.9, 0.9),
-                            ..default()
+                    parent.spawn(TextBundle::from_section("Play", text_style(40.0)));
+                });
+            let button_colors = ButtonColors::from(&config.ui.button);
+            children
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(240.0),
+                            height: Val::Px(50.0),
+                            margin: UiRect::top(Val::Px(10.)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
                         },
-                    ));
+                        background_color: button_colors.normal.into(),
+                        ..Default::default()
+                    },
+                    button_colors,
+                    ChangeState(GameState::Achievements),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Achievements", text_style(40.0)));
                 });
+            if server_address.is_some() {
+                let button_colors = ButtonColors::from(&config.ui.button);
+                children
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(240.0),
+                                height: Val::Px(50.0),
+                                margin: UiRect::top(Val::Px(10.)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: button_colors.normal.into(),
+                            ..Default::default()
+                        },
+                        button_colors,
+                        ChangeState(GameState::Online),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("Play Online", text_style(40.0)));
+                    });
+            }
         });
     commands
         .spawn((
@@ -153,19 +177,12 @@ fn setup_menu(mut commands: Commands, textures: Res<LogoAssets>) {
                     },
                     ButtonColors {
                         normal: Color::NONE,
-                        ..default()
+                        ..ButtonColors::from(&config.ui.button)
                     },
                     OpenLink("https://bevyengine.org"),
                 ))
                 .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Made with Bevy",
-                        TextStyle {
-                            font_size: 15.0,
-                            color: Color::rgb(0.9, 0.9, 0.9),
-                            ..default()
-                        },
-                    ));
+                    parent.spawn(TextBundle::from_section("Made with Bevy", text_style(15.0)));
                     parent.spawn(ImageBundle {
                         image: textures.bevy.clone().into(),
                         style: Style {
@@ -198,14 +215,7 @@ fn setup_menu(mut commands: Commands, textures: Res<LogoAssets>) {
                     OpenLink("https://github.com/snendev/bevy_game_template"),
                 ))
                 .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Source code",
-                        TextStyle {
-                            font_size: 15.0,
-                            color: Color::rgb(0.9, 0.9, 0.9),
-                            ..default()
-                        },
-                    ));
+                    parent.spawn(TextBundle::from_section("Source code", text_style(15.0)));
                     parent.spawn(ImageBundle {
                         image: textures.github.clone().into(),
                         style: Style {
@@ -247,27 +257,6 @@ fn handle_logo_button_click(
     }
 }
 
-fn render_button_interactions(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &ButtonColors),
-        (Changed<Interaction>, With<Button>),
-    >,
-) {
-    for (interaction, mut color, button_colors) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                *color = button_colors.active.into();
-            }
-            Interaction::Hovered => {
-                *color = button_colors.hovered.into();
-            }
-            Interaction::None => {
-                *color = button_colors.normal.into();
-            }
-        }
-    }
-}
-
 fn cleanup_menu(mut commands: Commands, menu: Query<Entity, With<Menu>>) {
     for entity in menu.iter() {
         commands.entity(entity).despawn_recursive();
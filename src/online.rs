@@ -0,0 +1,79 @@
+use std::net::SocketAddr;
+
+use bevy::prelude::*;
+
+use bevy_game_controls::Controller;
+use bevy_game_gameplay::blueprints::CharacterMovement;
+use bevy_game_network::{ClientConnection, LocalInput, NetworkClientPlugin, NetworkId};
+
+use crate::GameState;
+
+pub struct OnlinePlugin;
+
+/// This plugin connects to the server in [`ServerAddress`] when entering `GameState::Online`
+/// Player one's controls are sent to the server, which replicates every character back,
+/// and pressing Escape disconnects and returns to the menu
+impl Plugin for OnlinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(NetworkClientPlugin)
+            .add_systems(OnEnter(GameState::Online), connect_to_server)
+            .add_systems(
+                Update,
+                leave_online_game.run_if(in_state(GameState::Online)),
+            )
+            .add_systems(OnExit(GameState::Online), disconnect_from_server);
+    }
+}
+
+// The server to join. When this resource exists, the menu offers to play online.
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource)]
+pub struct ServerAddress(pub SocketAddr);
+
+fn connect_to_server(
+    mut commands: Commands,
+    address: Option<Res<ServerAddress>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(address) = address else {
+        error!("No server address to connect to");
+        next_state.set(GameState::Menu);
+        return;
+    };
+    match ClientConnection::connect(address.0) {
+        Ok(connection) => {
+            info!("Connecting to {}", address.0);
+            commands.insert_resource(connection);
+            commands.spawn((
+                Name::new("Online Input"),
+                Controller::One,
+                CharacterMovement::default(),
+                LocalInput,
+            ));
+        }
+        Err(error) => {
+            error!("Failed to connect to {}: {error}", address.0);
+            next_state.set(GameState::Menu);
+        }
+    }
+}
+
+fn leave_online_game(inputs: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if inputs.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn disconnect_from_server(
+    mut commands: Commands,
+    connection: Option<Res<ClientConnection>>,
+    online_query: Query<Entity, Or<(With<LocalInput>, With<NetworkId>)>>,
+) {
+    if let Some(connection) = connection {
+        connection.disconnect();
+        commands.remove_resource::<ClientConnection>();
+    }
+    for entity in online_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
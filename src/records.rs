@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use bevy_game_controls::Controller;
+use bevy_game_gameplay::{
+    blueprints::{Character, Ghost},
+    LevelOutcome, LevelTimer,
+};
+
+use crate::{
+    level::{CurrentLevel, LevelResults, Levels},
+    storage, GameState,
+};
+
+const HIGH_SCORES_FILE: &str = "high_scores.ron";
+// Seconds between two recorded positions, the ghost is interpolated in between
+const SAMPLE_INTERVAL: f32 = 0.05;
+
+pub struct RecordsPlugin;
+
+/// This plugin keeps the local high scores of time-trial levels
+/// Player one's run is recorded while playing, and the fastest run of a level is saved next to
+/// the high scores to be replayed by a ghost the next time that level is played
+impl Plugin for RecordsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<HighScores>(HIGH_SCORES_FILE).unwrap_or_default())
+            .add_systems(OnEnter(GameState::Playing), (start_recording, spawn_ghost))
+            .add_systems(
+                Update,
+                (
+                    (record_run, play_ghost)
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(resource_exists::<LevelTimer>()),
+                    save_best_run.run_if(resource_exists_and_changed::<LevelResults>()),
+                ),
+            )
+            .add_systems(OnExit(GameState::Playing), despawn_ghost);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct HighScore {
+    // Seconds taken to finish the level
+    pub time: f32,
+}
+
+// The best finished run of every time-trial level, by level name
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Resource)]
+pub struct HighScores(pub BTreeMap<String, HighScore>);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+struct GhostSample {
+    time: f32,
+    position: Vec2,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct GhostRun {
+    samples: Vec<GhostSample>,
+}
+
+impl GhostRun {
+    fn position_at(&self, time: f32) -> Option<Vec2> {
+        let next = self.samples.partition_point(|sample| sample.time <= time);
+        let previous = next
+            .checked_sub(1)
+            .and_then(|index| self.samples.get(index));
+        match (previous, self.samples.get(next)) {
+            (Some(previous), Some(next)) => Some(previous.position.lerp(
+                next.position,
+                (time - previous.time) / (next.time - previous.time),
+            )),
+            (Some(sample), None) | (None, Some(sample)) => Some(sample.position),
+            (None, None) => None,
+        }
+    }
+}
+
+// The run currently being played, only recorded in time-trial levels
+#[derive(Debug)]
+#[derive(Resource)]
+struct RunRecording {
+    level: &'static str,
+    run: GhostRun,
+}
+
+#[derive(Debug)]
+#[derive(Resource)]
+struct GhostPlayback(GhostRun);
+
+fn ghost_file(level: &str) -> String {
+    let name: String = level
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{name}.ghost.ron")
+}
+
+fn start_recording(mut commands: Commands, levels: Res<Levels>, current_level: Res<CurrentLevel>) {
+    match levels.get(current_level.0) {
+        Some(level) if level.time_trial => commands.insert_resource(RunRecording {
+            level: level.name,
+            run: GhostRun::default(),
+        }),
+        _ => commands.remove_resource::<RunRecording>(),
+    }
+}
+
+fn record_run(
+    recording: Option<ResMut<RunRecording>>,
+    timer: Res<LevelTimer>,
+    player_query: Query<(&Controller, &Transform), With<Character>>,
+) {
+    let Some(mut recording) = recording else {
+        return;
+    };
+    let time = timer.0.elapsed_secs();
+    if let Some(last) = recording.run.samples.last() {
+        if time - last.time < SAMPLE_INTERVAL {
+            return;
+        }
+    }
+    let Some((_, transform)) = player_query
+        .iter()
+        .find(|(controller, _)| matches!(controller, Controller::One))
+    else {
+        return;
+    };
+    recording.run.samples.push(GhostSample {
+        time,
+        position: transform.translation.truncate(),
+    });
+}
+
+fn save_best_run(
+    mut high_scores: ResMut<HighScores>,
+    results: Res<LevelResults>,
+    recording: Option<Res<RunRecording>>,
+) {
+    let Some(recording) = recording else {
+        return;
+    };
+    if results.outcome != LevelOutcome::Won || recording.level != results.name {
+        return;
+    }
+    let time = results.elapsed.as_secs_f32();
+    if let Some(best) = high_scores.0.get(results.name) {
+        if best.time <= time {
+            return;
+        }
+    }
+    info!("New best time on \"{}\": {time:.2}s", results.name);
+    high_scores
+        .0
+        .insert(results.name.to_string(), HighScore { time });
+    storage::save(&ghost_file(results.name), &recording.run);
+    storage::save(HIGH_SCORES_FILE, &*high_scores);
+}
+
+fn spawn_ghost(
+    mut commands: Commands,
+    high_scores: Res<HighScores>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+) {
+    let Some(level) = levels.get(current_level.0) else {
+        return;
+    };
+    if !level.time_trial || !high_scores.0.contains_key(level.name) {
+        return;
+    }
+    let Some(run) = storage::load::<GhostRun>(&ghost_file(level.name)) else {
+        return;
+    };
+    let Some(position) = run.position_at(0.) else {
+        return;
+    };
+    commands.spawn(Ghost::bundle(position));
+    commands.insert_resource(GhostPlayback(run));
+}
+
+fn play_ghost(
+    playback: Option<Res<GhostPlayback>>,
+    timer: Res<LevelTimer>,
+    mut ghost_query: Query<&mut Transform, With<Ghost>>,
+) {
+    let Some(playback) = playback else {
+        return;
+    };
+    let Some(position) = playback.0.position_at(timer.0.elapsed_secs()) else {
+        return;
+    };
+    for mut transform in ghost_query.iter_mut() {
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+fn despawn_ghost(mut commands: Commands, ghost_query: Query<Entity, With<Ghost>>) {
+    for entity in ghost_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<GhostPlayback>();
+}
@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+
+use bevy_game_gameplay::{blueprints::GameConfig, LevelOutcome};
+
+use crate::{
+    level::{CurrentLevel, LevelResults, Levels},
+    ui::{format_time, text_style, ButtonColors},
+    GameState,
+};
+
+pub struct ResultsPlugin;
+
+/// This plugin draws the results of the last level with buttons to retry, continue or leave
+/// The screen is only drawn during the States `GameState::LevelComplete` and `GameState::GameOver`
+impl Plugin for ResultsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::LevelComplete), setup_results)
+            .add_systems(OnEnter(GameState::GameOver), setup_results)
+            .add_systems(
+                Update,
+                handle_results_button_click.run_if(
+                    in_state(GameState::LevelComplete).or_else(in_state(GameState::GameOver)),
+                ),
+            )
+            .add_systems(OnExit(GameState::LevelComplete), cleanup_results)
+            .add_systems(OnExit(GameState::GameOver), cleanup_results);
+    }
+}
+
+#[derive(Component)]
+struct Results;
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Component)]
+enum ResultsAction {
+    Retry,
+    NextLevel,
+    Menu,
+}
+
+impl ResultsAction {
+    fn label(&self) -> &'static str {
+        match self {
+            ResultsAction::Retry => "Retry",
+            ResultsAction::NextLevel => "Next Level",
+            ResultsAction::Menu => "Menu",
+        }
+    }
+}
+
+fn setup_results(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    results: Option<Res<LevelResults>>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+) {
+    let Some(results) = results else {
+        warn!("No level results to display");
+        return;
+    };
+    let won = results.outcome == LevelOutcome::Won;
+    let mut actions = vec![ResultsAction::Retry];
+    if won && levels.has_next(&current_level) {
+        actions.push(ResultsAction::NextLevel);
+    }
+    actions.push(ResultsAction::Menu);
+
+    commands
+        .spawn((
+            Name::new("Results"),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            },
+            Results,
+        ))
+        .with_children(|children| {
+            children.spawn(TextBundle::from_section(
+                if won { "Level Complete" } else { "Game Over" },
+                text_style(60.0),
+            ));
+            children.spawn(TextBundle::from_section(results.name, text_style(30.0)));
+
+            let mut stats = vec![
+                format!("Time: {}", format_time(results.elapsed.as_secs_f32())),
+                format!("Pickups: {}", results.stats.pickups),
+                format!("Deaths: {}", results.stats.deaths),
+            ];
+            stats.extend(
+                results
+                    .scores
+                    .iter()
+                    .map(|(controller, score)| format!("{}: {}", controller.label(), score)),
+            );
+            for line in stats {
+                children.spawn(TextBundle::from_section(line, text_style(20.0)));
+            }
+
+            children
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(10.),
+                        margin: UiRect::top(Val::Px(20.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|children| {
+                    for action in actions {
+                        let button_colors = ButtonColors::from(&config.ui.button);
+                        children
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        width: Val::Px(180.0),
+                                        height: Val::Px(50.0),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: button_colors.normal.into(),
+                                    ..Default::default()
+                                },
+                                button_colors,
+                                action,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    action.label(),
+                                    text_style(30.0),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+fn handle_results_button_click(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut current_level: ResMut<CurrentLevel>,
+    interaction_query: Query<(&Interaction, &ResultsAction), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, action) in interaction_query.iter() {
+        if let Interaction::Pressed = *interaction {
+            match action {
+                ResultsAction::Retry => {
+                    next_state.set(GameState::Playing);
+                }
+                ResultsAction::NextLevel => {
+                    current_level.0 += 1;
+                    next_state.set(GameState::Playing);
+                }
+                ResultsAction::Menu => {
+                    current_level.0 = 0;
+                    next_state.set(GameState::Menu);
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_results(mut commands: Commands, results: Query<Entity, With<Results>>) {
+    for entity in results.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
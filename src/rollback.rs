@@ -0,0 +1,109 @@
+use std::net::SocketAddr;
+
+use bevy::prelude::*;
+
+use bevy_game_controls::{Controller, ControllerSet};
+use bevy_game_gameplay::blueprints::{Character, RollbackPlayer};
+use bevy_game_network::{RollbackMode, RollbackPlugin, RollbackSession};
+
+use crate::GameState;
+
+// Player one and player two
+const PLAYERS: usize = 2;
+// The controller levels spawn each player index with
+const CONTROLLERS: [Controller; PLAYERS] = [Controller::One, Controller::Two];
+// Players a level has no position for are spawned this far from the previous player
+const PLAYER_SPACING: Vec2 = Vec2::new(200., 0.);
+
+pub struct RollbackModePlugin;
+
+/// This plugin runs every level in a rollback session when [`RollbackSettings`] exists
+/// The session starts when entering `GameState::Playing` and ends when that state is exited
+impl Plugin for RollbackModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RollbackPlugin)
+            .add_systems(
+                OnEnter(GameState::Playing),
+                start_rollback_session.run_if(resource_exists::<RollbackSettings>()),
+            )
+            .add_systems(
+                PreUpdate,
+                assign_rollback_players
+                    .before(ControllerSet)
+                    .run_if(resource_exists::<RollbackSession>()),
+            )
+            .add_systems(OnExit(GameState::Playing), end_rollback_session);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[derive(Resource)]
+pub enum RollbackSettings {
+    // Re-simulates this many frames every frame to find non-deterministic gameplay
+    SyncTest { check_distance: u32 },
+    PeerToPeer { local: SocketAddr, peer: SocketAddr },
+}
+
+fn start_rollback_session(
+    mut commands: Commands,
+    settings: Res<RollbackSettings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    match *settings {
+        RollbackSettings::SyncTest { check_distance } => {
+            info!("Starting a sync-test session checking {check_distance} frames");
+            commands.insert_resource(RollbackSession::sync_test(PLAYERS, check_distance));
+        }
+        RollbackSettings::PeerToPeer { local, peer } => {
+            match RollbackSession::peer_to_peer(local, peer) {
+                Ok(session) => {
+                    info!("Starting a rollback session on {local} with {peer}");
+                    commands.insert_resource(session);
+                }
+                Err(error) => {
+                    error!("Failed to start a rollback session on {local}: {error}");
+                    next_state.set(GameState::Menu);
+                }
+            }
+        }
+    }
+}
+
+// A rollback session simulates every player, so levels made for fewer players get the others added
+pub(crate) fn rollback_players(players: &[(Controller, Vec2)]) -> Vec<(Controller, Vec2)> {
+    let mut players = players.to_vec();
+    if let Some(&(_, mut position)) = players.last() {
+        for controller in CONTROLLERS.iter().skip(players.len()) {
+            position += PLAYER_SPACING;
+            players.push((*controller, position));
+        }
+    }
+    players
+}
+
+// Levels place each player the same way on every peer, so the controller a character is spawned with
+// gives its player index. The local player is then controlled like player one, whichever index it has.
+fn assign_rollback_players(
+    mut commands: Commands,
+    session: Res<RollbackSession>,
+    player_query: Query<(Entity, &Controller), (With<Character>, Without<RollbackPlayer>)>,
+) {
+    let local_player = match session.mode() {
+        RollbackMode::SyncTest { .. } => 0,
+        RollbackMode::PeerToPeer { local_player, .. } => *local_player,
+    };
+    for (entity, controller) in player_query.iter() {
+        let player = match controller {
+            Controller::One => 0,
+            Controller::Two => 1,
+        };
+        commands.entity(entity).insert((
+            RollbackPlayer(player),
+            CONTROLLERS[(player + PLAYERS - local_player) % PLAYERS],
+        ));
+    }
+}
+
+fn end_rollback_session(mut commands: Commands) {
+    commands.remove_resource::<RollbackSession>();
+}
@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use bevy::{
+    prelude::*,
+    scene::{serde::SceneDeserializer, DynamicSceneBuilder, SceneFilter, SceneSpawnError},
+    utils::HashMap,
+};
+use serde::de::DeserializeSeed;
+use thiserror::Error;
+
+use bevy_game_controls::Controller;
+use bevy_game_gameplay::{
+    blueprints::{
+        BlueprintRegistry, Character, CharacterFacing, CharacterMovement, Collider, ExitZone,
+        Health, InputSource, MovementContribution, MovementProfile, MovementProfileSource, Pickup,
+        PickupKind, PickupRespawn, Score, Terrain, TerrainKind, Velocity, Wall,
+    },
+    LevelObjective,
+};
+
+pub struct LevelScenePlugin;
+
+/// This plugin registers the types stored in level scene files, so they can be loaded
+/// in release builds as well as debug builds
+impl Plugin for LevelScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Character>()
+            .register_type::<CharacterMovement>()
+            .register_type::<MovementContribution>()
+            .register_type::<Vec<MovementContribution>>()
+            .register_type::<InputSource>()
+            .register_type::<Velocity>()
+            .register_type::<MovementProfile>()
+            .register_type::<MovementProfileSource>()
+            .register_type::<CharacterFacing>()
+            .register_type::<Health>()
+            .register_type::<Score>()
+            .register_type::<Collider>()
+            .register_type::<Pickup>()
+            .register_type::<PickupKind>()
+            .register_type::<PickupRespawn>()
+            .register_type::<ExitZone>()
+            .register_type::<Terrain>()
+            .register_type::<TerrainKind>()
+            .register_type::<Wall>()
+            .register_type::<Controller>()
+            .register_type::<LevelObjective>();
+    }
+}
+
+// The asset source that level files outside the assets folder are read from
+#[cfg(not(target_arch = "wasm32"))]
+const LEVEL_FILE_SOURCE: &str = "level_file";
+
+// A scene file to play as the first level, as an asset path such as `levels/editor.scn.ron`
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct SceneLevel(pub String);
+
+impl SceneLevel {
+    // A path to a file on disk, such as `editor/assets/levels/editor.scn.ron`, is read from an asset source
+    // of its folder, and any other path is an asset path
+    // Asset sources are built by `AssetPlugin`, so this is called before `DefaultPlugins` are added
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path(app: &mut App, path: String) -> SceneLevel {
+        use bevy::asset::io::{file::FileAssetReader, AssetSourceBuilder};
+
+        let file = match Path::new(&path).canonicalize() {
+            Ok(file) if file.is_file() => file,
+            _ => return SceneLevel(path),
+        };
+        let (Some(folder), Some(name)) = (file.parent(), file.file_name()) else {
+            return SceneLevel(path);
+        };
+        let folder = folder.to_path_buf();
+        app.register_asset_source(
+            LEVEL_FILE_SOURCE,
+            AssetSourceBuilder::default()
+                .with_reader(move || Box::new(FileAssetReader::new(folder.clone()))),
+        );
+        SceneLevel(format!("{LEVEL_FILE_SOURCE}://{}", name.to_string_lossy()))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LevelSceneError {
+    #[error("Could not access scene file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not serialize scene: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("Could not parse scene: {0}")]
+    Deserialize(#[from] ron::error::SpannedError),
+    #[error("Could not spawn scene: {0}")]
+    Spawn(#[from] SceneSpawnError),
+}
+
+// Entities made from a registered blueprint make up a level
+// Anything else, such as UI or editor entities, is not saved
+fn level_entities(world: &World) -> Vec<Entity> {
+    let registry = world.resource::<BlueprintRegistry>();
+    world
+        .iter_entities()
+        .filter(|entity| registry.matches(entity))
+        .map(|entity| entity.id())
+        .collect()
+}
+
+// The level entities and objective of the world, as a scene
+// Only the components that blueprints insert are extracted
+pub fn snapshot_level(world: &World) -> DynamicScene {
+    let entities = level_entities(world);
+    let filter = world
+        .resource::<BlueprintRegistry>()
+        .iter()
+        .flat_map(|blueprint| blueprint.components().iter().copied())
+        .fold(SceneFilter::deny_all(), |filter, component| {
+            filter.allow_by_id(component)
+        });
+    DynamicSceneBuilder::from_world(world)
+        .with_filter(filter)
+        .deny_all_resources()
+        .allow_resource::<LevelObjective>()
+        .extract_entities(entities.into_iter())
+        .extract_resources()
+        .build()
+}
+
+// Replaces the level entities of the world with the ones in a scene
+pub fn restore_level(world: &mut World, scene: &DynamicScene) -> Result<(), LevelSceneError> {
+    for entity in level_entities(world) {
+        world.entity_mut(entity).despawn_recursive();
+    }
+    scene.write_to_world(world, &mut HashMap::default())?;
+    Ok(())
+}
+
+// Writes the level entities and objective of the world to a scene file
+pub fn save_level_scene(world: &mut World, path: &Path) -> Result<(), LevelSceneError> {
+    let scene = snapshot_level(world);
+    let contents = scene.serialize_ron(world.resource::<AppTypeRegistry>())?;
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+    std::fs::write(path, contents)?;
+    info!("Saved level scene to {}", path.display());
+    Ok(())
+}
+
+// Replaces the level entities of the world with the ones in a scene file
+pub fn load_level_scene(world: &mut World, path: &Path) -> Result<(), LevelSceneError> {
+    let contents = std::fs::read_to_string(path)?;
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let scene = {
+        let registry = type_registry.read();
+        let mut deserializer = ron::de::Deserializer::from_str(&contents)?;
+        SceneDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|error| deserializer.span_error(error))?
+    };
+    restore_level(world, &scene)?;
+    info!("Loaded level scene from {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+// Local save files are written to this directory, next to wherever the game is started from
+// The web build has no file system, so nothing is persisted there
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_DIRECTORY: &str = "saves";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load<T: DeserializeOwned>(file: &str) -> Option<T> {
+    let path = std::path::Path::new(SAVE_DIRECTORY).join(file);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(error) => {
+            warn!("Failed to read {}: {error}", path.display());
+            return None;
+        }
+    };
+    match ron::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Failed to parse {}: {error}", path.display());
+            None
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save<T: Serialize>(file: &str, value: &T) {
+    let path = std::path::Path::new(SAVE_DIRECTORY).join(file);
+    let contents = match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            error!("Failed to serialize {}: {error}", path.display());
+            return;
+        }
+    };
+    if let Err(error) =
+        std::fs::create_dir_all(SAVE_DIRECTORY).and_then(|_| std::fs::write(&path, contents))
+    {
+        error!("Failed to write {}: {error}", path.display());
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load<T: DeserializeOwned>(_file: &str) -> Option<T> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save<T: Serialize>(_file: &str, _value: &T) {}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use bevy_game_gameplay::blueprints::ButtonConfig;
+
+pub struct UiPlugin;
+
+/// This plugin provides the interactions shared by every screen built from these primitives
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_button_interactions);
+    }
+}
+
+pub const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+
+pub fn text_style(font_size: f32) -> TextStyle {
+    TextStyle {
+        font_size,
+        color: TEXT_COLOR,
+        ..default()
+    }
+}
+
+// Formats seconds as `mm:ss.s`
+pub fn format_time(seconds: f32) -> String {
+    format!(
+        "{:02}:{:04.1}",
+        (seconds / 60.).floor() as u32,
+        seconds % 60.
+    )
+}
+
+#[derive(Component)]
+pub struct ButtonColors {
+    pub normal: Color,
+    pub hovered: Color,
+    pub active: Color,
+}
+
+impl From<&ButtonConfig> for ButtonColors {
+    fn from(config: &ButtonConfig) -> Self {
+        ButtonColors {
+            normal: config.normal,
+            hovered: config.hovered,
+            active: config.active,
+        }
+    }
+}
+
+fn render_button_interactions(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &ButtonColors),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color, button_colors) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = button_colors.active.into();
+            }
+            Interaction::Hovered => {
+                *color = button_colors.hovered.into();
+            }
+            Interaction::None => {
+                *color = button_colors.normal.into();
+            }
+        }
+    }
+}